@@ -0,0 +1,249 @@
+//! Drives arbitrary sequences of borrows against every backend sharing one storage and checks
+//! them against a model of the documented borrow rules.
+//!
+//! Every acquisition uses a fresh wrapper over the shared storage, which is how aliasing
+//! wrappers such as [`OwnOrBorrow::RefCellRef`] are used in practice. The model asserts that
+//! shared borrows never coexist with an exclusive one, that failures are reported exactly when
+//! a conflicting guard is alive, and that poisoned locks keep failing with
+//! [`LockError::Poisoned`].
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use atomic_refcell::AtomicRefCell;
+use libfuzzer_sys::fuzz_target;
+use own_or_borrow::{
+    BorrowBackend, LockError, OwnOrAtomicBorrow, OwnOrBorrow, OwnOrLock, OwnOrSpin,
+};
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+
+/// The number of wrappers available to one input, bounding the number of acquisitions.
+const WRAPPERS: usize = 64;
+
+#[derive(Arbitrary, Debug)]
+enum Backend {
+    RefCell,
+    AtomicRefCell,
+    RwLock,
+    Mutex,
+    ParkingLotRwLock,
+    ParkingLotMutex,
+    SpinRwLock,
+    SpinMutex,
+}
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    /// Attempts a shared borrow through a fresh wrapper.
+    Borrow,
+    /// Attempts an exclusive borrow through a fresh wrapper.
+    BorrowMut,
+    /// Drops the live guard at the given index, modulo the number of guards.
+    Release(u8),
+    /// Writes through the live exclusive guard, if any.
+    Write(u32),
+    /// Panics while holding an exclusive guard, if the backend can be poisoned.
+    Poison,
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    backend: Backend,
+    ops: Vec<Op>,
+}
+
+/// How a backend treats concurrent borrows.
+struct Rules<B: BorrowBackend> {
+    /// Whether shared borrows can coexist, i.e. the backend is not a mutex.
+    shared_readers: bool,
+    /// Whether a panic while holding an exclusive guard poisons the storage.
+    poisons: bool,
+    /// Whether an error reports poisoning.
+    is_poisoned: fn(&B::Error) -> bool,
+}
+
+enum Guard<'w, B: BorrowBackend + 'w> {
+    Shared(B::Ref<'w>),
+    Exclusive(B::RefMut<'w>),
+}
+
+fn run<B>(wrappers: &mut [B], rules: Rules<B>, ops: &[Op])
+where
+    B: BorrowBackend<Target = u32>,
+{
+    let mut free = wrappers.iter_mut();
+    let mut guards: Vec<Guard<'_, B>> = Vec::new();
+    let mut value = 0;
+    let mut poisoned = false;
+
+    for op in ops {
+        let exclusive = guards
+            .iter()
+            .any(|guard| matches!(guard, Guard::Exclusive(_)));
+        match op {
+            Op::Borrow => {
+                let Some(wrapper) = free.next() else { break };
+                let expected =
+                    !poisoned && !exclusive && (rules.shared_readers || guards.is_empty());
+                match wrapper.try_borrow() {
+                    Ok(guard) => {
+                        assert!(expected, "shared borrow aliases a live guard");
+                        assert_eq!(*guard, value);
+                        guards.push(Guard::Shared(guard));
+                    }
+                    Err(error) => {
+                        assert!(
+                            !expected,
+                            "shared borrow failed without a conflicting guard"
+                        );
+                        assert_eq!((rules.is_poisoned)(&error), poisoned);
+                    }
+                }
+            }
+            Op::BorrowMut => {
+                let Some(wrapper) = free.next() else { break };
+                let expected = !poisoned && guards.is_empty();
+                match wrapper.try_borrow_mut() {
+                    Ok(guard) => {
+                        assert!(expected, "exclusive borrow aliases a live guard");
+                        assert_eq!(*guard, value);
+                        guards.push(Guard::Exclusive(guard));
+                    }
+                    Err(error) => {
+                        assert!(
+                            !expected,
+                            "exclusive borrow failed without a conflicting guard"
+                        );
+                        assert_eq!((rules.is_poisoned)(&error), poisoned);
+                    }
+                }
+            }
+            Op::Release(index) => {
+                if !guards.is_empty() {
+                    guards.swap_remove(usize::from(*index) % guards.len());
+                }
+            }
+            Op::Write(new) => {
+                if let Some(Guard::Exclusive(guard)) = guards.first_mut() {
+                    **guard = *new;
+                    value = *new;
+                }
+            }
+            Op::Poison => {
+                if !rules.poisons || poisoned || !guards.is_empty() {
+                    continue;
+                }
+                let Some(wrapper) = free.next() else { break };
+
+                // The fuzzer's panic hook aborts the process, so it is swapped out while the
+                // guard is dropped during unwinding.
+                let hook = panic::take_hook();
+                panic::set_hook(Box::new(|_| {}));
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    let _guard = wrapper.try_borrow_mut();
+                    panic!("poisoning the storage");
+                }));
+                panic::set_hook(hook);
+
+                assert!(result.is_err());
+                poisoned = true;
+            }
+        }
+    }
+
+    drop(guards);
+    if let Some(wrapper) = free.next() {
+        match wrapper.try_borrow() {
+            Ok(guard) => {
+                assert!(!poisoned, "poisoned storage can be borrowed");
+                assert_eq!(*guard, value);
+            }
+            Err(error) => assert!(poisoned && (rules.is_poisoned)(&error)),
+        }
+    }
+}
+
+fn lock_rules<'a>(poisons: bool) -> Rules<OwnOrLock<'a, u32>> {
+    Rules {
+        shared_readers: true,
+        poisons,
+        is_poisoned: |error| matches!(error, LockError::Poisoned),
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let ops = &input.ops;
+    match input.backend {
+        Backend::RefCell => {
+            let cell = RefCell::new(0);
+            let mut wrappers: Vec<_> = (0..WRAPPERS).map(|_| OwnOrBorrow::from(&cell)).collect();
+            let rules = Rules {
+                shared_readers: true,
+                poisons: false,
+                is_poisoned: |_| false,
+            };
+            run(&mut wrappers, rules, ops);
+        }
+        Backend::AtomicRefCell => {
+            let cell = AtomicRefCell::new(0);
+            let mut wrappers: Vec<_> = (0..WRAPPERS)
+                .map(|_| OwnOrAtomicBorrow::from(&cell))
+                .collect();
+            let rules = Rules {
+                shared_readers: true,
+                poisons: false,
+                is_poisoned: |_| false,
+            };
+            run(&mut wrappers, rules, ops);
+        }
+        Backend::RwLock => {
+            let lock = std::sync::RwLock::new(0);
+            let mut wrappers: Vec<_> = (0..WRAPPERS).map(|_| OwnOrLock::from(&lock)).collect();
+            run(&mut wrappers, lock_rules(true), ops);
+        }
+        Backend::Mutex => {
+            let mutex = std::sync::Mutex::new(0);
+            let mut wrappers: Vec<_> = (0..WRAPPERS).map(|_| OwnOrLock::from(&mutex)).collect();
+            let rules = Rules {
+                shared_readers: false,
+                ..lock_rules(true)
+            };
+            run(&mut wrappers, rules, ops);
+        }
+        Backend::ParkingLotRwLock => {
+            let lock = parking_lot::RwLock::new(0);
+            let mut wrappers: Vec<_> = (0..WRAPPERS).map(|_| OwnOrLock::from(&lock)).collect();
+            run(&mut wrappers, lock_rules(false), ops);
+        }
+        Backend::ParkingLotMutex => {
+            let mutex = parking_lot::Mutex::new(0);
+            let mut wrappers: Vec<_> = (0..WRAPPERS).map(|_| OwnOrLock::from(&mutex)).collect();
+            let rules = Rules {
+                shared_readers: false,
+                ..lock_rules(false)
+            };
+            run(&mut wrappers, rules, ops);
+        }
+        Backend::SpinRwLock => {
+            let lock = spin::RwLock::new(0);
+            let mut wrappers: Vec<_> = (0..WRAPPERS).map(|_| OwnOrSpin::from(&lock)).collect();
+            let rules = Rules {
+                shared_readers: true,
+                poisons: false,
+                is_poisoned: |error| matches!(error, LockError::Poisoned),
+            };
+            run(&mut wrappers, rules, ops);
+        }
+        Backend::SpinMutex => {
+            let mutex = spin::Mutex::new(0);
+            let mut wrappers: Vec<_> = (0..WRAPPERS).map(|_| OwnOrSpin::from(&mutex)).collect();
+            let rules = Rules {
+                shared_readers: false,
+                poisons: false,
+                is_poisoned: |error| matches!(error, LockError::Poisoned),
+            };
+            run(&mut wrappers, rules, ops);
+        }
+    }
+});
@@ -0,0 +1,144 @@
+//! Arithmetic and bitwise operators delegating to the inner value.
+
+use crate::{OwnOrBorrow, Reference, ReferenceMut};
+use core::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Deref,
+    DerefMut, Div, DivAssign, Mul, MulAssign, Neg, Not, Rem, RemAssign, Shl, ShlAssign, Shr,
+    ShrAssign, Sub, SubAssign,
+};
+
+macro_rules! impl_guard_binary_op {
+    ($guard:ident, $($op:ident::$method:ident),+) => {
+        $(
+            impl<'a, T, Rhs> $op<Rhs> for $guard<'a, T>
+            where
+                T: Copy + $op<Rhs>,
+            {
+                type Output = T::Output;
+
+                #[inline]
+                fn $method(self, rhs: Rhs) -> Self::Output {
+                    T::$method(*self.deref(), rhs)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_guard_unary_op {
+    ($guard:ident, $($op:ident::$method:ident),+) => {
+        $(
+            impl<'a, T> $op for $guard<'a, T>
+            where
+                T: Copy + $op,
+            {
+                type Output = T::Output;
+
+                #[inline]
+                fn $method(self) -> Self::Output {
+                    T::$method(*self.deref())
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_assign_op {
+    ($($op:ident::$method:ident),+) => {
+        $(
+            impl<'a, T, Rhs> $op<Rhs> for ReferenceMut<'a, T>
+            where
+                T: $op<Rhs> + ?Sized,
+            {
+                #[inline]
+                fn $method(&mut self, rhs: Rhs) {
+                    T::$method(self.deref_mut(), rhs)
+                }
+            }
+
+            /// ## Panics
+            /// Panics if the value is currently borrowed, or if it is a plain shared reference
+            /// ([`OwnOrBorrow::Borrowed`]).
+            impl<'a, T, Rhs> $op<Rhs> for OwnOrBorrow<'a, T>
+            where
+                T: $op<Rhs>,
+            {
+                #[inline]
+                #[track_caller]
+                fn $method(&mut self, rhs: Rhs) {
+                    T::$method(&mut self.borrow_mut(), rhs)
+                }
+            }
+        )+
+    };
+}
+
+impl_guard_binary_op!(
+    Reference,
+    Add::add,
+    Sub::sub,
+    Mul::mul,
+    Div::div,
+    Rem::rem,
+    BitAnd::bitand,
+    BitOr::bitor,
+    BitXor::bitxor,
+    Shl::shl,
+    Shr::shr
+);
+impl_guard_binary_op!(
+    ReferenceMut,
+    Add::add,
+    Sub::sub,
+    Mul::mul,
+    Div::div,
+    Rem::rem,
+    BitAnd::bitand,
+    BitOr::bitor,
+    BitXor::bitxor,
+    Shl::shl,
+    Shr::shr
+);
+impl_guard_unary_op!(Reference, Neg::neg, Not::not);
+impl_guard_unary_op!(ReferenceMut, Neg::neg, Not::not);
+impl_assign_op!(
+    AddAssign::add_assign,
+    SubAssign::sub_assign,
+    MulAssign::mul_assign,
+    DivAssign::div_assign,
+    RemAssign::rem_assign,
+    BitAndAssign::bitand_assign,
+    BitOrAssign::bitor_assign,
+    BitXorAssign::bitxor_assign,
+    ShlAssign::shl_assign,
+    ShrAssign::shr_assign
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    #[test]
+    fn assign_through_wrapper() {
+        let cell = RefCell::new(1.0);
+        let mut accumulator = OwnOrBorrow::from(&cell);
+        accumulator += 2.0;
+        accumulator *= 3.0;
+        assert_eq!(*cell.borrow(), 9.0);
+    }
+
+    #[test]
+    fn guard_operators() {
+        let value = OwnOrBorrow::own(6);
+        assert_eq!(value.borrow() * 7, 42);
+        assert_eq!(-value.borrow(), -6);
+
+        let cell = RefCell::new(0b1010_u8);
+        let mut value = OwnOrBorrow::from(&cell);
+        let mut guard = value.borrow_mut();
+        guard |= 0b0101;
+        assert_eq!(!guard, 0b1111_0000);
+        assert_eq!(*cell.borrow(), 0b1111);
+    }
+}
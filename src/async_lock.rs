@@ -0,0 +1,295 @@
+//! Asynchronous counterpart to [`OwnOrLock`](crate::OwnOrLock) built on `tokio` locks.
+
+use core::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use tokio::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A type that provides either an owned value or a `tokio` [`RwLock`] or [`Mutex`] protected
+/// value, accessed asynchronously.
+///
+/// ```
+/// use own_or_borrow::OwnOrBorrowAsync;
+/// use tokio::sync::RwLock;
+///
+/// async fn increment(mut value: OwnOrBorrowAsync<'_, u32>) {
+///     *value.borrow_mut().await += 1;
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let lock = RwLock::new(42);
+/// increment(OwnOrBorrowAsync::from(&lock)).await;
+/// increment(OwnOrBorrowAsync::own(0)).await;
+/// assert_eq!(*lock.read().await, 43);
+/// # });
+/// ```
+pub enum OwnOrBorrowAsync<'a, T> {
+    /// An owned value.
+    Owned(T),
+    /// An owned [`RwLock`].
+    RwLock(RwLock<T>),
+    /// A reference to a [`RwLock`].
+    RwLockRef(&'a RwLock<T>),
+    /// A shared, reference-counted [`RwLock`]. The wrapper keeps the value alive.
+    RwLockArc(Arc<RwLock<T>>),
+    /// An owned [`Mutex`].
+    Mutex(Mutex<T>),
+    /// A reference to a [`Mutex`].
+    MutexRef(&'a Mutex<T>),
+    /// A shared, reference-counted [`Mutex`]. The wrapper keeps the value alive.
+    MutexArc(Arc<Mutex<T>>),
+}
+
+/// A reference to owned or asynchronously locked data.
+pub enum AsyncReference<'a, T> {
+    /// A reference to an owned value.
+    Borrowed(&'a T),
+    /// A [`RwLock`] read guard.
+    RwLock(RwLockReadGuard<'a, T>),
+    /// A [`Mutex`] guard.
+    Mutex(MutexGuard<'a, T>),
+}
+
+/// A mutable reference to owned or asynchronously locked data.
+pub enum AsyncReferenceMut<'a, T> {
+    /// A mutable reference to an owned value.
+    Borrowed(&'a mut T),
+    /// A [`RwLock`] write guard.
+    RwLock(RwLockWriteGuard<'a, T>),
+    /// A [`Mutex`] guard.
+    Mutex(MutexGuard<'a, T>),
+}
+
+impl<'a, T> OwnOrBorrowAsync<'a, T> {
+    /// Initializes a new instance that owns data.
+    pub const fn own(value: T) -> Self {
+        Self::Owned(value)
+    }
+
+    /// Borrows the inner value, waiting until the lock can be acquired.
+    ///
+    /// A [`Mutex`] is locked exclusively.
+    pub async fn borrow(&self) -> AsyncReference<'_, T> {
+        match self {
+            OwnOrBorrowAsync::Owned(value) => AsyncReference::Borrowed(value),
+            OwnOrBorrowAsync::RwLock(lock) => lock.read().await.into(),
+            OwnOrBorrowAsync::RwLockRef(lock) => lock.read().await.into(),
+            OwnOrBorrowAsync::RwLockArc(lock) => lock.read().await.into(),
+            OwnOrBorrowAsync::Mutex(mutex) => mutex.lock().await.into(),
+            OwnOrBorrowAsync::MutexRef(mutex) => mutex.lock().await.into(),
+            OwnOrBorrowAsync::MutexArc(mutex) => mutex.lock().await.into(),
+        }
+    }
+
+    /// Borrows the inner value mutably, waiting until the lock can be acquired.
+    ///
+    /// Owned locks are accessed without locking since `&mut self` already guarantees
+    /// exclusive access.
+    pub async fn borrow_mut(&mut self) -> AsyncReferenceMut<'_, T> {
+        match self {
+            OwnOrBorrowAsync::Owned(value) => AsyncReferenceMut::Borrowed(value),
+            OwnOrBorrowAsync::RwLock(lock) => lock.get_mut().into(),
+            OwnOrBorrowAsync::RwLockRef(lock) => lock.write().await.into(),
+            OwnOrBorrowAsync::RwLockArc(lock) => lock.write().await.into(),
+            OwnOrBorrowAsync::Mutex(mutex) => mutex.get_mut().into(),
+            OwnOrBorrowAsync::MutexRef(mutex) => mutex.lock().await.into(),
+            OwnOrBorrowAsync::MutexArc(mutex) => mutex.lock().await.into(),
+        }
+    }
+}
+
+impl<'a, T> From<RwLock<T>> for OwnOrBorrowAsync<'a, T> {
+    #[inline]
+    fn from(value: RwLock<T>) -> Self {
+        Self::RwLock(value)
+    }
+}
+
+impl<'a, T> From<&'a RwLock<T>> for OwnOrBorrowAsync<'a, T> {
+    #[inline]
+    fn from(value: &'a RwLock<T>) -> Self {
+        Self::RwLockRef(value)
+    }
+}
+
+impl<'a, T> From<Arc<RwLock<T>>> for OwnOrBorrowAsync<'a, T> {
+    #[inline]
+    fn from(value: Arc<RwLock<T>>) -> Self {
+        Self::RwLockArc(value)
+    }
+}
+
+impl<'a, T> From<Mutex<T>> for OwnOrBorrowAsync<'a, T> {
+    #[inline]
+    fn from(value: Mutex<T>) -> Self {
+        Self::Mutex(value)
+    }
+}
+
+impl<'a, T> From<&'a Mutex<T>> for OwnOrBorrowAsync<'a, T> {
+    #[inline]
+    fn from(value: &'a Mutex<T>) -> Self {
+        Self::MutexRef(value)
+    }
+}
+
+impl<'a, T> From<Arc<Mutex<T>>> for OwnOrBorrowAsync<'a, T> {
+    #[inline]
+    fn from(value: Arc<Mutex<T>>) -> Self {
+        Self::MutexArc(value)
+    }
+}
+
+impl<'a, T> Deref for AsyncReference<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            AsyncReference::Borrowed(value) => value,
+            AsyncReference::RwLock(guard) => guard.deref(),
+            AsyncReference::Mutex(guard) => guard.deref(),
+        }
+    }
+}
+
+impl<'a, T> Deref for AsyncReferenceMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            AsyncReferenceMut::Borrowed(value) => value,
+            AsyncReferenceMut::RwLock(guard) => guard.deref(),
+            AsyncReferenceMut::Mutex(guard) => guard.deref(),
+        }
+    }
+}
+
+impl<'a, T> DerefMut for AsyncReferenceMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            AsyncReferenceMut::Borrowed(value) => value,
+            AsyncReferenceMut::RwLock(guard) => guard.deref_mut(),
+            AsyncReferenceMut::Mutex(guard) => guard.deref_mut(),
+        }
+    }
+}
+
+impl<'a, T> core::borrow::Borrow<T> for AsyncReference<'a, T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> core::borrow::Borrow<T> for AsyncReferenceMut<'a, T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> core::borrow::BorrowMut<T> for AsyncReferenceMut<'a, T> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+impl<'a, T> AsRef<T> for AsyncReference<'a, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> AsRef<T> for AsyncReferenceMut<'a, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> AsMut<T> for AsyncReferenceMut<'a, T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+impl<'a, T> From<&'a T> for AsyncReference<'a, T> {
+    #[inline]
+    fn from(value: &'a T) -> Self {
+        AsyncReference::Borrowed(value)
+    }
+}
+
+impl<'a, T> From<&'a mut T> for AsyncReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: &'a mut T) -> Self {
+        AsyncReferenceMut::Borrowed(value)
+    }
+}
+
+impl<'a, T> From<RwLockReadGuard<'a, T>> for AsyncReference<'a, T> {
+    #[inline]
+    fn from(value: RwLockReadGuard<'a, T>) -> Self {
+        AsyncReference::RwLock(value)
+    }
+}
+
+impl<'a, T> From<MutexGuard<'a, T>> for AsyncReference<'a, T> {
+    #[inline]
+    fn from(value: MutexGuard<'a, T>) -> Self {
+        AsyncReference::Mutex(value)
+    }
+}
+
+impl<'a, T> From<RwLockWriteGuard<'a, T>> for AsyncReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: RwLockWriteGuard<'a, T>) -> Self {
+        AsyncReferenceMut::RwLock(value)
+    }
+}
+
+impl<'a, T> From<MutexGuard<'a, T>> for AsyncReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: MutexGuard<'a, T>) -> Self {
+        AsyncReferenceMut::Mutex(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn borrow_owned_type() {
+        let mut value = OwnOrBorrowAsync::own(42);
+        assert_eq!(value.borrow().await.as_ref(), &42);
+        *value.borrow_mut().await = 17;
+        assert_eq!(value.borrow().await.as_ref(), &17);
+    }
+
+    #[tokio::test]
+    async fn borrow_rwlock_ref() {
+        let lock = RwLock::new(42);
+        let mut value = OwnOrBorrowAsync::from(&lock);
+        *value.borrow_mut().await += 1;
+        assert_eq!(value.borrow().await.as_ref(), &43);
+        assert_eq!(*lock.read().await, 43);
+    }
+
+    #[tokio::test]
+    async fn borrow_mutex_arc_across_tasks() {
+        let mutex = Arc::new(Mutex::new(0));
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let mut value = OwnOrBorrowAsync::from(mutex.clone());
+                tokio::spawn(async move { *value.borrow_mut().await += 1 })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(*mutex.lock().await, 4);
+    }
+}
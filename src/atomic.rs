@@ -0,0 +1,407 @@
+//! [`Sync`] counterpart to [`OwnOrBorrow`](crate::OwnOrBorrow) with run-time borrow checking.
+
+use crate::AtomicBorrowFault;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+use atomic_refcell::{AtomicRef, AtomicRefCell, AtomicRefMut};
+use core::ops::{Deref, DerefMut};
+
+/// A type that provides either an owned value or an [`AtomicRefCell`] borrowed reference to
+/// a value.
+///
+/// This mirrors [`OwnOrBorrow`](crate::OwnOrBorrow), but the [`AtomicRefCell`] tracks borrows
+/// with atomic counters. The wrapper is therefore [`Sync`] whenever `T` is [`Send`] and
+/// [`Sync`], and can be shared across threads while keeping `RefCell`-like semantics.
+/// Conflicting borrows still panic rather than block.
+///
+/// ```
+/// use own_or_borrow::OwnOrAtomicBorrow;
+/// use atomic_refcell::AtomicRefCell;
+///
+/// let cell = AtomicRefCell::new(42);
+/// let value = OwnOrAtomicBorrow::from(&cell);
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| assert_eq!(*value.borrow(), 42));
+///     s.spawn(|| assert_eq!(*value.borrow(), 42));
+/// });
+/// ```
+pub enum OwnOrAtomicBorrow<'a, T> {
+    /// An owned value.
+    Owned(T),
+    /// An owned [`AtomicRefCell`].
+    AtomicRefCell(AtomicRefCell<T>),
+    /// A reference to an [`AtomicRefCell`].
+    AtomicRefCellRef(&'a AtomicRefCell<T>),
+    /// A plain shared reference. The value cannot be borrowed mutably.
+    Borrowed(&'a T),
+    /// A plain mutable reference.
+    BorrowedMut(&'a mut T),
+    /// A shared, reference-counted [`AtomicRefCell`]. The wrapper keeps the value alive.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    AtomicRefCellArc(Arc<AtomicRefCell<T>>),
+}
+
+/// A reference to owned or [`AtomicRefCell`] borrowed data.
+pub enum AtomicReference<'a, T: ?Sized> {
+    /// A reference to a plain value.
+    Borrowed(&'a T),
+    /// An [`AtomicRefCell`] borrow guard.
+    AtomicRefCell(AtomicRef<'a, T>),
+}
+
+/// A mutable reference to owned or [`AtomicRefCell`] borrowed data.
+pub enum AtomicReferenceMut<'a, T: ?Sized> {
+    /// A mutable reference to a plain value.
+    Borrowed(&'a mut T),
+    /// An [`AtomicRefCell`] mutable borrow guard.
+    AtomicRefCell(AtomicRefMut<'a, T>),
+}
+
+impl<'a, T> OwnOrAtomicBorrow<'a, T> {
+    /// Initializes a new instance that owns data.
+    pub const fn own(value: T) -> Self {
+        Self::Owned(value)
+    }
+
+    /// Initializes a new instance from a plain shared reference.
+    ///
+    /// Since the value is only shared, [`borrow_mut`](Self::borrow_mut) will panic and
+    /// [`try_borrow_mut`](Self::try_borrow_mut) will fail with [`AtomicBorrowFault::Shared`].
+    pub const fn borrowed(value: &'a T) -> Self {
+        Self::Borrowed(value)
+    }
+
+    /// Initializes a new instance from a plain mutable reference.
+    pub const fn borrowed_mut(value: &'a mut T) -> Self {
+        Self::BorrowedMut(value)
+    }
+
+    /// Initializes a new instance that owns data wrapped in an [`AtomicRefCell`].
+    ///
+    /// As this can be evaluated at compile time and the type is [`Sync`], it can initialize
+    /// statics directly:
+    ///
+    /// ```
+    /// use own_or_borrow::OwnOrAtomicBorrow;
+    ///
+    /// static CONFIG: OwnOrAtomicBorrow<'static, u32> = OwnOrAtomicBorrow::new_refcell(3);
+    ///
+    /// assert_eq!(*CONFIG.borrow(), 3);
+    /// ```
+    pub const fn new_refcell(value: T) -> Self {
+        Self::AtomicRefCell(AtomicRefCell::new(value))
+    }
+
+    /// Initializes a new instance from a reference to an [`AtomicRefCell`].
+    ///
+    /// This is the `const` counterpart to `OwnOrAtomicBorrow::from(&cell)`.
+    pub const fn from_refcell_ref(value: &'a AtomicRefCell<T>) -> Self {
+        Self::AtomicRefCellRef(value)
+    }
+
+    /// Borrows the inner value.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn borrow(&self) -> AtomicReference<'_, T> {
+        match self {
+            OwnOrAtomicBorrow::Owned(value) => AtomicReference::Borrowed(value),
+            OwnOrAtomicBorrow::AtomicRefCell(cell) => cell.borrow().into(),
+            OwnOrAtomicBorrow::AtomicRefCellRef(cell) => cell.borrow().into(),
+            OwnOrAtomicBorrow::Borrowed(value) => AtomicReference::Borrowed(value),
+            OwnOrAtomicBorrow::BorrowedMut(value) => AtomicReference::Borrowed(value),
+            #[cfg(feature = "alloc")]
+            OwnOrAtomicBorrow::AtomicRefCellArc(cell) => cell.borrow().into(),
+        }
+    }
+
+    /// Borrows the inner value mutably.
+    ///
+    /// Owned cells are accessed without run-time checks since `&mut self` already guarantees
+    /// exclusive access.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed, or if it is a plain shared reference
+    /// ([`OwnOrAtomicBorrow::Borrowed`]).
+    #[track_caller]
+    pub fn borrow_mut(&mut self) -> AtomicReferenceMut<'_, T> {
+        match self {
+            OwnOrAtomicBorrow::Owned(value) => AtomicReferenceMut::Borrowed(value),
+            OwnOrAtomicBorrow::AtomicRefCell(cell) => cell.get_mut().into(),
+            OwnOrAtomicBorrow::AtomicRefCellRef(cell) => cell.borrow_mut().into(),
+            OwnOrAtomicBorrow::Borrowed(_) => panic!("{}", AtomicBorrowFault::Shared),
+            OwnOrAtomicBorrow::BorrowedMut(value) => AtomicReferenceMut::Borrowed(value),
+            #[cfg(feature = "alloc")]
+            OwnOrAtomicBorrow::AtomicRefCellArc(cell) => cell.borrow_mut().into(),
+        }
+    }
+
+    /// Attempts to borrow the inner value.
+    ///
+    /// Unlike [`borrow`](Self::borrow), this returns an error instead of panicking if the
+    /// value is currently mutably borrowed.
+    pub fn try_borrow(&self) -> Result<AtomicReference<'_, T>, AtomicBorrowFault> {
+        match self {
+            OwnOrAtomicBorrow::Owned(value) => Ok(AtomicReference::Borrowed(value)),
+            OwnOrAtomicBorrow::AtomicRefCell(cell) => Ok(cell.try_borrow()?.into()),
+            OwnOrAtomicBorrow::AtomicRefCellRef(cell) => Ok(cell.try_borrow()?.into()),
+            OwnOrAtomicBorrow::Borrowed(value) => Ok(AtomicReference::Borrowed(value)),
+            OwnOrAtomicBorrow::BorrowedMut(value) => Ok(AtomicReference::Borrowed(value)),
+            #[cfg(feature = "alloc")]
+            OwnOrAtomicBorrow::AtomicRefCellArc(cell) => Ok(cell.try_borrow()?.into()),
+        }
+    }
+
+    /// Attempts to borrow the inner value mutably.
+    ///
+    /// Unlike [`borrow_mut`](Self::borrow_mut), this returns an error instead of panicking if
+    /// the value is currently borrowed or is a plain shared reference.
+    pub fn try_borrow_mut(&mut self) -> Result<AtomicReferenceMut<'_, T>, AtomicBorrowFault> {
+        match self {
+            OwnOrAtomicBorrow::Owned(value) => Ok(AtomicReferenceMut::Borrowed(value)),
+            OwnOrAtomicBorrow::AtomicRefCell(cell) => Ok(cell.get_mut().into()),
+            OwnOrAtomicBorrow::AtomicRefCellRef(cell) => Ok(cell.try_borrow_mut()?.into()),
+            OwnOrAtomicBorrow::Borrowed(_) => Err(AtomicBorrowFault::Shared),
+            OwnOrAtomicBorrow::BorrowedMut(value) => Ok(AtomicReferenceMut::Borrowed(value)),
+            #[cfg(feature = "alloc")]
+            OwnOrAtomicBorrow::AtomicRefCellArc(cell) => Ok(cell.try_borrow_mut()?.into()),
+        }
+    }
+}
+
+impl<'a, T> From<AtomicRefCell<T>> for OwnOrAtomicBorrow<'a, T> {
+    #[inline]
+    fn from(value: AtomicRefCell<T>) -> Self {
+        Self::AtomicRefCell(value)
+    }
+}
+
+impl<'a, T> From<&'a AtomicRefCell<T>> for OwnOrAtomicBorrow<'a, T> {
+    #[inline]
+    fn from(value: &'a AtomicRefCell<T>) -> Self {
+        Self::AtomicRefCellRef(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T> From<Arc<AtomicRefCell<T>>> for OwnOrAtomicBorrow<'a, T> {
+    #[inline]
+    fn from(value: Arc<AtomicRefCell<T>>) -> Self {
+        Self::AtomicRefCellArc(value)
+    }
+}
+
+impl<'a, T> From<&'a mut T> for OwnOrAtomicBorrow<'a, T> {
+    #[inline]
+    fn from(value: &'a mut T) -> Self {
+        Self::BorrowedMut(value)
+    }
+}
+
+impl<'a, T: ?Sized> Deref for AtomicReference<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            AtomicReference::Borrowed(value) => value,
+            AtomicReference::AtomicRefCell(guard) => guard.deref(),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Deref for AtomicReferenceMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            AtomicReferenceMut::Borrowed(value) => value,
+            AtomicReferenceMut::AtomicRefCell(guard) => guard.deref(),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> DerefMut for AtomicReferenceMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            AtomicReferenceMut::Borrowed(value) => value,
+            AtomicReferenceMut::AtomicRefCell(guard) => guard.deref_mut(),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> core::borrow::Borrow<T> for AtomicReference<'a, T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T: ?Sized> core::borrow::Borrow<T> for AtomicReferenceMut<'a, T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T: ?Sized> core::borrow::BorrowMut<T> for AtomicReferenceMut<'a, T> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+impl<'a, T: ?Sized> AsRef<T> for AtomicReference<'a, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T: ?Sized> AsRef<T> for AtomicReferenceMut<'a, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T: ?Sized> AsMut<T> for AtomicReferenceMut<'a, T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+impl<'a, T: ?Sized> From<&'a T> for AtomicReference<'a, T> {
+    #[inline]
+    fn from(value: &'a T) -> Self {
+        AtomicReference::Borrowed(value)
+    }
+}
+
+impl<'a, T: ?Sized> From<&'a mut T> for AtomicReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: &'a mut T) -> Self {
+        AtomicReferenceMut::Borrowed(value)
+    }
+}
+
+impl<'a, T: ?Sized> From<AtomicRef<'a, T>> for AtomicReference<'a, T> {
+    #[inline]
+    fn from(value: AtomicRef<'a, T>) -> Self {
+        AtomicReference::AtomicRefCell(value)
+    }
+}
+
+impl<'a, T: ?Sized> From<AtomicRefMut<'a, T>> for AtomicReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: AtomicRefMut<'a, T>) -> Self {
+        AtomicReferenceMut::AtomicRefCell(value)
+    }
+}
+
+impl<'a, T> core::fmt::Debug for OwnOrAtomicBorrow<'a, T>
+where
+    T: core::fmt::Debug,
+{
+    /// Formats the inner value, or prints `<mutably borrowed>` if it is currently mutably
+    /// borrowed.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.try_borrow() {
+            Ok(data) => core::fmt::Debug::fmt(data.as_ref(), f),
+            Err(_) => f.write_str("<mutably borrowed>"),
+        }
+    }
+}
+
+impl<'a, T> core::fmt::Display for OwnOrAtomicBorrow<'a, T>
+where
+    T: core::fmt::Display,
+{
+    /// Formats the inner value, or prints `<mutably borrowed>` if it is currently mutably
+    /// borrowed.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.try_borrow() {
+            Ok(data) => core::fmt::Display::fmt(data.as_ref(), f),
+            Err(_) => f.write_str("<mutably borrowed>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    fn assert_sync<T: Sync>(_value: &T) {}
+
+    #[test]
+    fn borrow_owned_type() {
+        let mut value = OwnOrAtomicBorrow::own(42);
+        *value.borrow_mut() += 1;
+        assert_eq!(value.borrow().as_ref(), &43);
+    }
+
+    #[test]
+    fn static_refcell() {
+        static VALUE: OwnOrAtomicBorrow<'static, i32> = OwnOrAtomicBorrow::new_refcell(42);
+        assert_eq!(*VALUE.borrow(), 42);
+        assert!(VALUE.try_borrow().is_ok());
+    }
+
+    #[test]
+    fn borrow_atomic_refcell_ref() {
+        let cell = AtomicRefCell::new(42);
+        let mut value = OwnOrAtomicBorrow::from(&cell);
+        *value.borrow_mut() += 1;
+        assert_eq!(*cell.borrow(), 43);
+    }
+
+    #[test]
+    fn try_borrow_mut_fails_while_borrowed() {
+        let cell = AtomicRefCell::new(42);
+        let mut value = OwnOrAtomicBorrow::from(&cell);
+        let _guard = cell.borrow();
+        assert!(matches!(
+            value.try_borrow_mut(),
+            Err(AtomicBorrowFault::BorrowMut(_))
+        ));
+        assert!(value.try_borrow().is_ok());
+    }
+
+    #[test]
+    fn try_borrow_mut_fails_for_shared_reference() {
+        let mut value = OwnOrAtomicBorrow::borrowed(&42);
+        assert!(matches!(
+            value.try_borrow_mut(),
+            Err(AtomicBorrowFault::Shared)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn share_across_threads() {
+        let cell = AtomicRefCell::new(0);
+        let value = OwnOrAtomicBorrow::from(&cell);
+        assert_sync(&value);
+
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| assert_eq!(*value.borrow(), 0));
+            }
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn arc_keeps_value_alive() {
+        let cell = Arc::new(AtomicRefCell::new(42));
+        let mut value = OwnOrAtomicBorrow::from(cell.clone());
+        *value.borrow_mut() += 1;
+        drop(value);
+        assert_eq!(*cell.borrow(), 43);
+    }
+}
@@ -0,0 +1,153 @@
+//! Borrow audit events.
+//!
+//! When the `defmt-audit` feature is enabled, borrow acquisitions and conflicts are emitted
+//! as `defmt::trace!` events naming the wrapper type and its storage. Without the feature, all
+//! functions in this module compile down to the plain [`RefCell`] accesses.
+//!
+//! Releases are not traced: the guards are plain enums without a `Drop` implementation, which
+//! keeps them destructurable by the projections such as [`Reference::map`](crate::Reference::map).
+//!
+//! Note that `defmt` filters `trace` level events by default; set e.g.
+//! `DEFMT_LOG=own_or_borrow=trace` when building the firmware to see them.
+
+use core::cell::{BorrowError, BorrowMutError, Ref, RefCell, RefMut};
+
+/// The wrapper type a borrow is taken through.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt-audit", derive(defmt::Format))]
+pub(crate) enum Wrapper {
+    OwnOrBorrow,
+    OwnOrBorrowUnsized,
+    LazyOwnOrBorrow,
+    ScopedBorrow,
+}
+
+/// The storage a borrow is served from.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt-audit", derive(defmt::Format))]
+pub(crate) enum Source {
+    /// [`OwnOrBorrow::Owned`](crate::OwnOrBorrow::Owned)
+    Owned,
+    /// [`OwnOrBorrow::RefCell`](crate::OwnOrBorrow::RefCell)
+    RefCell,
+    /// [`OwnOrBorrow::RefCellRef`](crate::OwnOrBorrow::RefCellRef)
+    RefCellRef,
+    /// [`OwnOrBorrow::Borrowed`](crate::OwnOrBorrow::Borrowed)
+    Borrowed,
+    /// [`OwnOrBorrow::BorrowedMut`](crate::OwnOrBorrow::BorrowedMut)
+    BorrowedMut,
+    /// [`OwnOrBorrow::RefCellRc`](crate::OwnOrBorrow::RefCellRc)
+    #[cfg(feature = "alloc")]
+    RefCellRc,
+    /// [`OwnOrBorrowUnsized::Boxed`](crate::OwnOrBorrowUnsized::Boxed)
+    #[cfg(feature = "alloc")]
+    Boxed,
+    /// [`LazyOwnOrBorrow::Lazy`](crate::LazyOwnOrBorrow::Lazy)
+    Lazy,
+}
+
+impl Source {
+    /// Describes the storage for panic messages.
+    pub(crate) fn describe(self) -> &'static str {
+        match self {
+            Source::Owned => "owned value",
+            Source::RefCell => "owned RefCell",
+            Source::RefCellRef => "referenced RefCell",
+            Source::Borrowed => "shared reference",
+            Source::BorrowedMut => "mutable reference",
+            #[cfg(feature = "alloc")]
+            Source::RefCellRc => "reference-counted RefCell",
+            #[cfg(feature = "alloc")]
+            Source::Boxed => "boxed value",
+            Source::Lazy => "lazy value",
+        }
+    }
+}
+
+/// Records a borrow of a value that requires no run-time checks.
+#[inline(always)]
+pub(crate) fn unchecked(wrapper: Wrapper, source: Source, exclusive: bool) {
+    #[cfg(feature = "defmt-audit")]
+    if exclusive {
+        defmt::trace!("{}: exclusive borrow acquired ({})", wrapper, source);
+    } else {
+        defmt::trace!("{}: shared borrow acquired ({})", wrapper, source);
+    }
+
+    #[cfg(not(feature = "defmt-audit"))]
+    let _ = (wrapper, source, exclusive);
+}
+
+/// Attempts to borrow the cell immutably, recording the acquisition or conflict.
+#[inline(always)]
+pub(crate) fn try_borrow<T: ?Sized>(
+    wrapper: Wrapper,
+    cell: &RefCell<T>,
+    source: Source,
+) -> Result<Ref<'_, T>, BorrowError> {
+    let result = cell.try_borrow();
+
+    #[cfg(feature = "defmt-audit")]
+    match result {
+        Ok(_) => defmt::trace!("{}: shared borrow acquired ({})", wrapper, source),
+        Err(_) => defmt::trace!("{}: shared borrow conflict ({})", wrapper, source),
+    }
+
+    #[cfg(not(feature = "defmt-audit"))]
+    let _ = (wrapper, source);
+
+    result
+}
+
+/// Attempts to borrow the cell mutably, recording the acquisition or conflict.
+#[inline(always)]
+pub(crate) fn try_borrow_mut<T: ?Sized>(
+    wrapper: Wrapper,
+    cell: &RefCell<T>,
+    source: Source,
+) -> Result<RefMut<'_, T>, BorrowMutError> {
+    let result = cell.try_borrow_mut();
+
+    #[cfg(feature = "defmt-audit")]
+    match result {
+        Ok(_) => defmt::trace!("{}: exclusive borrow acquired ({})", wrapper, source),
+        Err(_) => defmt::trace!("{}: exclusive borrow conflict ({})", wrapper, source),
+    }
+
+    #[cfg(not(feature = "defmt-audit"))]
+    let _ = (wrapper, source);
+
+    result
+}
+
+/// Borrows the cell immutably, recording the acquisition or conflict.
+///
+/// ## Panics
+/// Panics if the value is currently mutably borrowed, just like [`RefCell::borrow`]. The
+/// message names the storage and the location points at the caller of the public API.
+#[inline(always)]
+#[track_caller]
+pub(crate) fn borrow<T: ?Sized>(wrapper: Wrapper, cell: &RefCell<T>, source: Source) -> Ref<'_, T> {
+    match try_borrow(wrapper, cell, source) {
+        Ok(value) => value,
+        Err(error) => panic!("{} ({})", error, source.describe()),
+    }
+}
+
+/// Borrows the cell mutably, recording the acquisition or conflict.
+///
+/// ## Panics
+/// Panics if the value is currently borrowed, just like [`RefCell::borrow_mut`]. The
+/// message names the storage and the location points at the caller of the public API.
+#[inline(always)]
+#[track_caller]
+pub(crate) fn borrow_mut<T: ?Sized>(
+    wrapper: Wrapper,
+    cell: &RefCell<T>,
+    source: Source,
+) -> RefMut<'_, T> {
+    match try_borrow_mut(wrapper, cell, source) {
+        Ok(value) => value,
+        Err(error) => panic!("{} ({})", error, source.describe()),
+    }
+}
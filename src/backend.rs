@@ -0,0 +1,529 @@
+//! Abstraction over the storage types that hand out borrow guards.
+
+#[cfg(any(feature = "std", feature = "spin"))]
+use crate::LockError;
+#[cfg(feature = "atomic_refcell")]
+use crate::{AtomicBorrowFault, AtomicReference, AtomicReferenceMut, OwnOrAtomicBorrow};
+use crate::{
+    BorrowFault, LazyOwnOrBorrow, OwnOrBorrow, OwnOrBorrowMut, OwnOrBorrowUnsized, Reference,
+    ReferenceMut,
+};
+#[cfg(feature = "std")]
+use crate::{LockReference, LockReferenceMut, OwnOrLock};
+#[cfg(feature = "spin")]
+use crate::{OwnOrSpin, SpinReference, SpinReferenceMut};
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+use core::cell::{Ref, RefCell, RefMut};
+use core::fmt::Display;
+use core::ops::{Deref, DerefMut};
+
+/// A storage type that hands out shared and exclusive borrow guards.
+///
+/// This trait lets generic code accept any of the wrappers in this crate, as well as plain
+/// [`RefCell`] instances, and lets downstream crates plug in their own cell or lock types
+/// by implementing it for them. [`OwnOrBorrow`] itself is not generic over the backend; its
+/// storage stays a closed set of variants.
+///
+/// ```
+/// use own_or_borrow::{BorrowBackend, OwnOrBorrow};
+/// use core::cell::RefCell;
+///
+/// fn increment<B: BorrowBackend<Target = u32>>(backend: &mut B) {
+///     *backend.borrow_mut() += 1;
+/// }
+///
+/// let cell = RefCell::new(1);
+/// let mut value = OwnOrBorrow::from(&cell);
+/// increment(&mut value);
+/// increment(&mut OwnOrBorrow::own(1));
+///
+/// assert_eq!(*cell.borrow(), 2);
+/// ```
+pub trait BorrowBackend {
+    /// The borrowed value.
+    type Target: ?Sized;
+
+    /// The shared borrow guard.
+    type Ref<'b>: Deref<Target = Self::Target>
+    where
+        Self: 'b;
+
+    /// The exclusive borrow guard.
+    type RefMut<'b>: DerefMut<Target = Self::Target>
+    where
+        Self: 'b;
+
+    /// The error returned if a borrow cannot be acquired.
+    type Error: Display;
+
+    /// Attempts to borrow the value.
+    fn try_borrow(&self) -> Result<Self::Ref<'_>, Self::Error>;
+
+    /// Attempts to borrow the value mutably.
+    fn try_borrow_mut(&mut self) -> Result<Self::RefMut<'_>, Self::Error>;
+
+    /// Borrows the value.
+    ///
+    /// ## Panics
+    /// Panics if [`try_borrow`](Self::try_borrow) fails.
+    #[track_caller]
+    fn borrow(&self) -> Self::Ref<'_> {
+        match self.try_borrow() {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Borrows the value mutably.
+    ///
+    /// ## Panics
+    /// Panics if [`try_borrow_mut`](Self::try_borrow_mut) fails.
+    #[track_caller]
+    fn borrow_mut(&mut self) -> Self::RefMut<'_> {
+        match self.try_borrow_mut() {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+impl<T: ?Sized> BorrowBackend for RefCell<T> {
+    type Target = T;
+    type Ref<'b>
+        = Ref<'b, T>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = RefMut<'b, T>
+    where
+        Self: 'b;
+    type Error = BorrowFault;
+
+    #[inline]
+    fn try_borrow(&self) -> Result<Self::Ref<'_>, Self::Error> {
+        Ok(RefCell::try_borrow(self)?)
+    }
+
+    #[inline]
+    fn try_borrow_mut(&mut self) -> Result<Self::RefMut<'_>, Self::Error> {
+        Ok(RefCell::try_borrow_mut(self)?)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow(&self) -> Self::Ref<'_> {
+        RefCell::borrow(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow_mut(&mut self) -> Self::RefMut<'_> {
+        RefCell::borrow_mut(self)
+    }
+}
+
+impl<T: ?Sized> BorrowBackend for &RefCell<T> {
+    type Target = T;
+    type Ref<'b>
+        = Ref<'b, T>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = RefMut<'b, T>
+    where
+        Self: 'b;
+    type Error = BorrowFault;
+
+    #[inline]
+    fn try_borrow(&self) -> Result<Self::Ref<'_>, Self::Error> {
+        Ok(RefCell::try_borrow(self)?)
+    }
+
+    #[inline]
+    fn try_borrow_mut(&mut self) -> Result<Self::RefMut<'_>, Self::Error> {
+        Ok(RefCell::try_borrow_mut(self)?)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow(&self) -> Self::Ref<'_> {
+        RefCell::borrow(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow_mut(&mut self) -> Self::RefMut<'_> {
+        RefCell::borrow_mut(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T: ?Sized> BorrowBackend for Rc<RefCell<T>> {
+    type Target = T;
+    type Ref<'b>
+        = Ref<'b, T>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = RefMut<'b, T>
+    where
+        Self: 'b;
+    type Error = BorrowFault;
+
+    #[inline]
+    fn try_borrow(&self) -> Result<Self::Ref<'_>, Self::Error> {
+        Ok(RefCell::try_borrow(self)?)
+    }
+
+    #[inline]
+    fn try_borrow_mut(&mut self) -> Result<Self::RefMut<'_>, Self::Error> {
+        Ok(RefCell::try_borrow_mut(self)?)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow(&self) -> Self::Ref<'_> {
+        RefCell::borrow(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow_mut(&mut self) -> Self::RefMut<'_> {
+        RefCell::borrow_mut(self)
+    }
+}
+
+impl<'a, T> BorrowBackend for OwnOrBorrow<'a, T> {
+    type Target = T;
+    type Ref<'b>
+        = Reference<'b, T>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = ReferenceMut<'b, T>
+    where
+        Self: 'b;
+    type Error = BorrowFault;
+
+    #[inline]
+    fn try_borrow(&self) -> Result<Self::Ref<'_>, Self::Error> {
+        OwnOrBorrow::try_borrow(self)
+    }
+
+    #[inline]
+    fn try_borrow_mut(&mut self) -> Result<Self::RefMut<'_>, Self::Error> {
+        OwnOrBorrow::try_borrow_mut(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow(&self) -> Self::Ref<'_> {
+        OwnOrBorrow::borrow(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow_mut(&mut self) -> Self::RefMut<'_> {
+        OwnOrBorrow::borrow_mut(self)
+    }
+}
+
+/// Borrowing an [`OwnOrBorrowMut`] never fails.
+impl<'a, T> BorrowBackend for OwnOrBorrowMut<'a, T> {
+    type Target = T;
+    type Ref<'b>
+        = Reference<'b, T>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = ReferenceMut<'b, T>
+    where
+        Self: 'b;
+    type Error = BorrowFault;
+
+    #[inline]
+    fn try_borrow(&self) -> Result<Self::Ref<'_>, Self::Error> {
+        Ok(OwnOrBorrowMut::borrow(self))
+    }
+
+    #[inline]
+    fn try_borrow_mut(&mut self) -> Result<Self::RefMut<'_>, Self::Error> {
+        Ok(OwnOrBorrowMut::borrow_mut(self))
+    }
+
+    #[inline]
+    fn borrow(&self) -> Self::Ref<'_> {
+        OwnOrBorrowMut::borrow(self)
+    }
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::RefMut<'_> {
+        OwnOrBorrowMut::borrow_mut(self)
+    }
+}
+
+impl<'a, T: ?Sized> BorrowBackend for OwnOrBorrowUnsized<'a, T> {
+    type Target = T;
+    type Ref<'b>
+        = Reference<'b, T>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = ReferenceMut<'b, T>
+    where
+        Self: 'b;
+    type Error = BorrowFault;
+
+    #[inline]
+    fn try_borrow(&self) -> Result<Self::Ref<'_>, Self::Error> {
+        OwnOrBorrowUnsized::try_borrow(self)
+    }
+
+    #[inline]
+    fn try_borrow_mut(&mut self) -> Result<Self::RefMut<'_>, Self::Error> {
+        OwnOrBorrowUnsized::try_borrow_mut(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow(&self) -> Self::Ref<'_> {
+        OwnOrBorrowUnsized::borrow(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow_mut(&mut self) -> Self::RefMut<'_> {
+        OwnOrBorrowUnsized::borrow_mut(self)
+    }
+}
+
+impl<'a, T, F> BorrowBackend for LazyOwnOrBorrow<'a, T, F>
+where
+    F: FnOnce() -> T,
+{
+    type Target = T;
+    type Ref<'b>
+        = Reference<'b, T>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = ReferenceMut<'b, T>
+    where
+        Self: 'b;
+    type Error = BorrowFault;
+
+    #[inline]
+    fn try_borrow(&self) -> Result<Self::Ref<'_>, Self::Error> {
+        LazyOwnOrBorrow::try_borrow(self)
+    }
+
+    #[inline]
+    fn try_borrow_mut(&mut self) -> Result<Self::RefMut<'_>, Self::Error> {
+        LazyOwnOrBorrow::try_borrow_mut(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow(&self) -> Self::Ref<'_> {
+        LazyOwnOrBorrow::borrow(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow_mut(&mut self) -> Self::RefMut<'_> {
+        LazyOwnOrBorrow::borrow_mut(self)
+    }
+}
+
+#[cfg(feature = "atomic_refcell")]
+#[cfg_attr(docsrs, doc(cfg(feature = "atomic_refcell")))]
+impl<'a, T> BorrowBackend for OwnOrAtomicBorrow<'a, T> {
+    type Target = T;
+    type Ref<'b>
+        = AtomicReference<'b, T>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = AtomicReferenceMut<'b, T>
+    where
+        Self: 'b;
+    type Error = AtomicBorrowFault;
+
+    #[inline]
+    fn try_borrow(&self) -> Result<Self::Ref<'_>, Self::Error> {
+        OwnOrAtomicBorrow::try_borrow(self)
+    }
+
+    #[inline]
+    fn try_borrow_mut(&mut self) -> Result<Self::RefMut<'_>, Self::Error> {
+        OwnOrAtomicBorrow::try_borrow_mut(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow(&self) -> Self::Ref<'_> {
+        OwnOrAtomicBorrow::borrow(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn borrow_mut(&mut self) -> Self::RefMut<'_> {
+        OwnOrAtomicBorrow::borrow_mut(self)
+    }
+}
+
+/// The `try_` methods never block and fail with [`LockError::WouldBlock`] if the lock is
+/// held. [`borrow`](BorrowBackend::borrow) and [`borrow_mut`](BorrowBackend::borrow_mut) block
+/// until the lock is acquired and panic only if the lock is poisoned.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<'a, T> BorrowBackend for OwnOrLock<'a, T> {
+    type Target = T;
+    type Ref<'b>
+        = LockReference<'b, T>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = LockReferenceMut<'b, T>
+    where
+        Self: 'b;
+    type Error = LockError;
+
+    #[inline]
+    fn try_borrow(&self) -> Result<Self::Ref<'_>, Self::Error> {
+        self.try_read()
+    }
+
+    #[inline]
+    fn try_borrow_mut(&mut self) -> Result<Self::RefMut<'_>, Self::Error> {
+        self.try_write()
+    }
+
+    #[track_caller]
+    fn borrow(&self) -> Self::Ref<'_> {
+        match self.read() {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    #[track_caller]
+    fn borrow_mut(&mut self) -> Self::RefMut<'_> {
+        match self.write() {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+/// The `try_` methods never spin and fail with [`LockError::WouldBlock`] if the lock is held.
+/// [`borrow`](BorrowBackend::borrow) and [`borrow_mut`](BorrowBackend::borrow_mut) spin until
+/// the lock is acquired and never fail.
+#[cfg(feature = "spin")]
+#[cfg_attr(docsrs, doc(cfg(feature = "spin")))]
+impl<'a, T> BorrowBackend for OwnOrSpin<'a, T> {
+    type Target = T;
+    type Ref<'b>
+        = SpinReference<'b, T>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = SpinReferenceMut<'b, T>
+    where
+        Self: 'b;
+    type Error = LockError;
+
+    #[inline]
+    fn try_borrow(&self) -> Result<Self::Ref<'_>, Self::Error> {
+        self.try_read().ok_or(LockError::WouldBlock)
+    }
+
+    #[inline]
+    fn try_borrow_mut(&mut self) -> Result<Self::RefMut<'_>, Self::Error> {
+        self.try_write().ok_or(LockError::WouldBlock)
+    }
+
+    #[inline]
+    fn borrow(&self) -> Self::Ref<'_> {
+        self.read()
+    }
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::RefMut<'_> {
+        self.write()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read<B: BorrowBackend<Target = i32>>(backend: &B) -> i32 {
+        *backend.borrow()
+    }
+
+    fn try_add<B: BorrowBackend<Target = i32>>(backend: &mut B, value: i32) -> bool {
+        match backend.try_borrow_mut() {
+            Ok(mut guard) => {
+                *guard += value;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    #[test]
+    fn refcell_backends() {
+        let mut cell = RefCell::new(1);
+        assert!(try_add(&mut cell, 1));
+        assert_eq!(read(&cell), 2);
+
+        let mut cell_ref = &cell;
+        assert!(try_add(&mut cell_ref, 1));
+        let _guard = cell.borrow();
+        assert!(!try_add(&mut cell_ref, 1));
+        assert_eq!(read(&cell_ref), 3);
+    }
+
+    #[test]
+    fn wrapper_backends() {
+        let mut value = OwnOrBorrow::own(1);
+        assert!(try_add(&mut value, 1));
+        assert_eq!(read(&value), 2);
+
+        let mut value = OwnOrBorrow::borrowed(&1);
+        assert!(!try_add(&mut value, 1));
+
+        let mut value = LazyOwnOrBorrow::lazy(|| 1);
+        assert!(try_add(&mut value, 1));
+        assert_eq!(read(&value), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn lock_backend() {
+        let lock = std::sync::RwLock::new(1);
+        let mut value = OwnOrLock::from(&lock);
+        assert!(try_add(&mut value, 1));
+        assert_eq!(read(&value), 2);
+
+        let _guard = lock.read().unwrap();
+        assert!(!try_add(&mut value, 1));
+        assert_eq!(read(&value), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "spin")]
+    fn spin_backend() {
+        let mutex = spin::Mutex::new(1);
+        let mut value = OwnOrSpin::from(&mutex);
+        assert!(try_add(&mut value, 1));
+
+        let guard = mutex.lock();
+        assert!(!try_add(&mut value, 1));
+        drop(guard);
+        assert_eq!(read(&value), 2);
+    }
+}
@@ -0,0 +1,196 @@
+//! Borrowing several values at once.
+
+use crate::{BorrowBackend, BorrowFault};
+
+/// A value that [`borrow_all`] can borrow, i.e. a shared or mutable reference to a
+/// [`BorrowBackend`] failing with [`BorrowFault`].
+///
+/// Shared references produce shared guards and mutable references produce mutable guards,
+/// so both can be mixed in one call.
+pub trait BorrowAllItem {
+    /// The borrow guard.
+    type Guard;
+
+    /// Attempts to borrow the value.
+    fn try_borrow_item(self) -> Result<Self::Guard, BorrowFault>;
+}
+
+/// A tuple or array of [`BorrowAllItem`]s, as accepted by [`borrow_all`].
+pub trait BorrowAll {
+    /// The tuple or array of borrow guards.
+    type Guards;
+
+    /// Attempts to borrow all values, releasing the already acquired borrows if one fails.
+    fn try_borrow_all(self) -> Result<Self::Guards, BorrowFault>;
+}
+
+impl<'b, B> BorrowAllItem for &'b B
+where
+    B: BorrowBackend<Error = BorrowFault> + ?Sized,
+{
+    type Guard = B::Ref<'b>;
+
+    #[inline]
+    fn try_borrow_item(self) -> Result<Self::Guard, BorrowFault> {
+        self.try_borrow()
+    }
+}
+
+impl<'b, B> BorrowAllItem for &'b mut B
+where
+    B: BorrowBackend<Error = BorrowFault> + ?Sized,
+{
+    type Guard = B::RefMut<'b>;
+
+    #[inline]
+    fn try_borrow_item(self) -> Result<Self::Guard, BorrowFault> {
+        self.try_borrow_mut()
+    }
+}
+
+macro_rules! impl_borrow_all_tuple {
+    ($($item:ident),+) => {
+        impl<$($item),+> BorrowAll for ($($item,)+)
+        where
+            $($item: BorrowAllItem),+
+        {
+            type Guards = ($($item::Guard,)+);
+
+            #[allow(non_snake_case)]
+            fn try_borrow_all(self) -> Result<Self::Guards, BorrowFault> {
+                let ($($item,)+) = self;
+                Ok(($($item.try_borrow_item()?,)+))
+            }
+        }
+    };
+}
+
+impl_borrow_all_tuple!(A);
+impl_borrow_all_tuple!(A, B);
+impl_borrow_all_tuple!(A, B, C);
+impl_borrow_all_tuple!(A, B, C, D);
+impl_borrow_all_tuple!(A, B, C, D, E);
+impl_borrow_all_tuple!(A, B, C, D, E, F);
+impl_borrow_all_tuple!(A, B, C, D, E, F, G);
+impl_borrow_all_tuple!(A, B, C, D, E, F, G, H);
+
+impl<I, const N: usize> BorrowAll for [I; N]
+where
+    I: BorrowAllItem,
+{
+    type Guards = [I::Guard; N];
+
+    fn try_borrow_all(self) -> Result<Self::Guards, BorrowFault> {
+        let mut fault = None;
+        let guards = self.map(|item| match fault {
+            Some(_) => None,
+            None => item
+                .try_borrow_item()
+                .map_err(|error| fault = Some(error))
+                .ok(),
+        });
+        match fault {
+            Some(error) => Err(error),
+            None => Ok(guards.map(|guard| match guard {
+                Some(guard) => guard,
+                None => unreachable!("all borrows succeeded"),
+            })),
+        }
+    }
+}
+
+/// Borrows several values at once, e.g. `borrow_all((&a, &mut b))`.
+///
+/// Shared references are borrowed with [`try_borrow`](BorrowBackend::try_borrow) and mutable
+/// references with [`try_borrow_mut`](BorrowBackend::try_borrow_mut). If any borrow fails,
+/// the borrows acquired so far are released, so either all or none of the values end up
+/// borrowed. This also catches wrappers that alias the same [`RefCell`](core::cell::RefCell).
+///
+/// ```
+/// use own_or_borrow::{try_borrow_all, BorrowFault, OwnOrBorrow};
+/// use core::cell::RefCell;
+///
+/// let cell = RefCell::new(1);
+/// let position = OwnOrBorrow::own(2);
+/// let mut velocity = OwnOrBorrow::from(&cell);
+///
+/// let (position, mut velocity) = try_borrow_all((&position, &mut velocity))?;
+/// *velocity += *position;
+/// assert_eq!(*velocity, 3);
+///
+/// let mut first = OwnOrBorrow::from(&cell);
+/// let mut second = OwnOrBorrow::from(&cell);
+/// drop(velocity);
+/// assert!(matches!(
+///     try_borrow_all([&mut first, &mut second]),
+///     Err(BorrowFault::BorrowMut(_))
+/// ));
+/// assert!(cell.try_borrow_mut().is_ok());
+/// # Ok::<(), BorrowFault>(())
+/// ```
+#[inline]
+pub fn try_borrow_all<B: BorrowAll>(values: B) -> Result<B::Guards, BorrowFault> {
+    values.try_borrow_all()
+}
+
+/// Borrows several values at once, like [`try_borrow_all`].
+///
+/// ## Panics
+/// Panics if any of the borrows fails.
+#[inline]
+#[track_caller]
+pub fn borrow_all<B: BorrowAll>(values: B) -> B::Guards {
+    match values.try_borrow_all() {
+        Ok(guards) => guards,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OwnOrBorrow;
+    use core::cell::RefCell;
+
+    #[test]
+    fn borrow_tuple_of_mixed_backends() {
+        let cell = RefCell::new(1);
+        let mut value = OwnOrBorrow::own(2);
+        let (shared, mut exclusive) = borrow_all((&cell, &mut value));
+        *exclusive += *shared;
+        drop((shared, exclusive));
+        assert_eq!(*value.borrow(), 3);
+    }
+
+    #[test]
+    fn failed_borrow_releases_all() {
+        let cell = RefCell::new(1);
+        let first = OwnOrBorrow::from(&cell);
+        let mut second = OwnOrBorrow::from(&cell);
+        assert!(matches!(
+            try_borrow_all((&first, &mut second)),
+            Err(BorrowFault::BorrowMut(_))
+        ));
+        assert!(cell.try_borrow_mut().is_ok());
+    }
+
+    #[test]
+    fn borrow_array() {
+        let values = [
+            OwnOrBorrow::own(1),
+            OwnOrBorrow::own(2),
+            OwnOrBorrow::own(3),
+        ];
+        let [a, b, c] = borrow_all([&values[0], &values[1], &values[2]]);
+        assert_eq!(*a + *b + *c, 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn borrow_all_panics_on_conflict() {
+        let cell = RefCell::new(1);
+        let mut first = OwnOrBorrow::from(&cell);
+        let mut second = OwnOrBorrow::from(&cell);
+        let _ = borrow_all([&mut first, &mut second]);
+    }
+}
@@ -0,0 +1,209 @@
+//! [`Cell`] counterpart to [`OwnOrBorrow`](crate::OwnOrBorrow) for [`Copy`] values.
+
+use core::cell::Cell;
+use core::ops::Deref;
+
+/// A type that provides either an owned or a borrowed [`Cell`].
+///
+/// Unlike [`OwnOrBorrow`](crate::OwnOrBorrow), there are no borrow flags to check: values
+/// are copied in and out, which suits small [`Copy`] payloads such as counters and flags.
+///
+/// ```
+/// use own_or_borrow::OwnOrCell;
+/// use core::cell::Cell;
+///
+/// fn tick(counter: &OwnOrCell<'_, u32>) {
+///     counter.update(|count| count + 1);
+/// }
+///
+/// let shared = Cell::new(0);
+/// tick(&OwnOrCell::from(&shared));
+/// tick(&OwnOrCell::own(0));
+///
+/// assert_eq!(shared.get(), 1);
+/// ```
+pub enum OwnOrCell<'a, T> {
+    /// An owned [`Cell`].
+    Cell(Cell<T>),
+    /// A reference to a [`Cell`].
+    CellRef(&'a Cell<T>),
+}
+
+/// A copy of the value of an [`OwnOrCell`], as returned by [`OwnOrCell::borrow`].
+///
+/// Later changes to the cell are not reflected in the snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CellSnapshot<T>(T);
+
+impl<'a, T> OwnOrCell<'a, T> {
+    /// Initializes a new instance that owns data.
+    pub const fn own(value: T) -> Self {
+        Self::Cell(Cell::new(value))
+    }
+
+    /// Initializes a new instance from a reference to a [`Cell`].
+    pub const fn from_cell_ref(value: &'a Cell<T>) -> Self {
+        Self::CellRef(value)
+    }
+
+    #[inline]
+    fn cell(&self) -> &Cell<T> {
+        match self {
+            OwnOrCell::Cell(cell) => cell,
+            OwnOrCell::CellRef(cell) => cell,
+        }
+    }
+
+    /// Sets the contained value.
+    #[inline]
+    pub fn set(&self, value: T) {
+        self.cell().set(value)
+    }
+
+    /// Replaces the contained value, returning the old value.
+    #[inline]
+    pub fn replace(&self, value: T) -> T {
+        self.cell().replace(value)
+    }
+
+    /// Returns a mutable reference to the value of an owned [`Cell`], or `None` for a
+    /// [`Cell`] reference.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        match self {
+            OwnOrCell::Cell(cell) => Some(cell.get_mut()),
+            OwnOrCell::CellRef(_) => None,
+        }
+    }
+}
+
+impl<'a, T: Copy> OwnOrCell<'a, T> {
+    /// Returns a copy of the contained value.
+    #[inline]
+    pub fn get(&self) -> T {
+        self.cell().get()
+    }
+
+    /// Updates the contained value using the closure and returns the new value.
+    #[inline]
+    pub fn update<F>(&self, f: F) -> T
+    where
+        F: FnOnce(T) -> T,
+    {
+        let value = f(self.get());
+        self.set(value);
+        value
+    }
+
+    /// Returns a snapshot of the contained value.
+    ///
+    /// This mirrors [`OwnOrBorrow::borrow`](crate::OwnOrBorrow::borrow) for code that only
+    /// needs to read the value, but never conflicts with other accesses.
+    #[inline]
+    pub fn borrow(&self) -> CellSnapshot<T> {
+        CellSnapshot(self.get())
+    }
+}
+
+impl<T> CellSnapshot<T> {
+    /// Returns the copied value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for CellSnapshot<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> AsRef<T> for CellSnapshot<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'a, T> From<Cell<T>> for OwnOrCell<'a, T> {
+    #[inline]
+    fn from(value: Cell<T>) -> Self {
+        Self::Cell(value)
+    }
+}
+
+impl<'a, T> From<&'a Cell<T>> for OwnOrCell<'a, T> {
+    #[inline]
+    fn from(value: &'a Cell<T>) -> Self {
+        Self::CellRef(value)
+    }
+}
+
+impl<'a, T: Default> Default for OwnOrCell<'a, T> {
+    fn default() -> Self {
+        Self::own(T::default())
+    }
+}
+
+impl<'a, T> core::fmt::Debug for OwnOrCell<'a, T>
+where
+    T: Copy + core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+impl<'a, T> core::fmt::Display for OwnOrCell<'a, T>
+where
+    T: Copy + core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.get(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn owned_cell() {
+        let mut value = OwnOrCell::own(1);
+        value.set(2);
+        assert_eq!(value.update(|value| value * 10), 20);
+        assert_eq!(value.replace(3), 20);
+        *value.get_mut().expect("cell is owned") += 1;
+        assert_eq!(value.get(), 4);
+    }
+
+    #[test]
+    fn cell_ref() {
+        let cell = Cell::new(true);
+        let mut value = OwnOrCell::from(&cell);
+        value.set(false);
+        assert!(!cell.get());
+        assert!(value.get_mut().is_none());
+    }
+
+    #[test]
+    fn snapshot_is_a_copy() {
+        let value = OwnOrCell::own(42);
+        let snapshot = value.borrow();
+        value.set(17);
+        assert_eq!(*snapshot, 42);
+        assert_eq!(value.borrow().into_inner(), 17);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn format() {
+        let value = OwnOrCell::own(42);
+        assert_eq!(format!("{:?}", value), "42");
+        assert_eq!(format!("{}", value), "42");
+    }
+}
@@ -0,0 +1,174 @@
+//! Comparison and hashing traits delegating to the inner value.
+
+use crate::{OwnOrBorrow, Reference, ReferenceMut};
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+impl<'a, 'b, T> PartialEq<OwnOrBorrow<'b, T>> for OwnOrBorrow<'a, T>
+where
+    T: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &OwnOrBorrow<'b, T>) -> bool {
+        T::eq(&self.borrow(), &other.borrow())
+    }
+}
+
+impl<'a, T> PartialEq<T> for OwnOrBorrow<'a, T>
+where
+    T: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &T) -> bool {
+        T::eq(&self.borrow(), other)
+    }
+}
+
+impl<'a, T> Eq for OwnOrBorrow<'a, T> where T: Eq {}
+
+impl<'a, 'b, T> PartialOrd<OwnOrBorrow<'b, T>> for OwnOrBorrow<'a, T>
+where
+    T: PartialOrd,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &OwnOrBorrow<'b, T>) -> Option<Ordering> {
+        T::partial_cmp(&self.borrow(), &other.borrow())
+    }
+}
+
+impl<'a, T> Ord for OwnOrBorrow<'a, T>
+where
+    T: Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        T::cmp(&self.borrow(), &other.borrow())
+    }
+}
+
+impl<'a, T> Hash for OwnOrBorrow<'a, T>
+where
+    T: Hash,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        T::hash(&self.borrow(), state)
+    }
+}
+
+macro_rules! impl_guard_cmp {
+    ($guard:ident) => {
+        impl<'a, 'b, T> PartialEq<$guard<'b, T>> for $guard<'a, T>
+        where
+            T: PartialEq + ?Sized,
+        {
+            #[inline]
+            fn eq(&self, other: &$guard<'b, T>) -> bool {
+                T::eq(self.deref(), other.deref())
+            }
+        }
+
+        impl<'a, T> PartialEq<T> for $guard<'a, T>
+        where
+            T: PartialEq + ?Sized,
+        {
+            #[inline]
+            fn eq(&self, other: &T) -> bool {
+                T::eq(self.deref(), other)
+            }
+        }
+
+        impl<'a, T> Eq for $guard<'a, T> where T: Eq + ?Sized {}
+
+        impl<'a, 'b, T> PartialOrd<$guard<'b, T>> for $guard<'a, T>
+        where
+            T: PartialOrd + ?Sized,
+        {
+            #[inline]
+            fn partial_cmp(&self, other: &$guard<'b, T>) -> Option<Ordering> {
+                T::partial_cmp(self.deref(), other.deref())
+            }
+        }
+
+        impl<'a, T> Ord for $guard<'a, T>
+        where
+            T: Ord + ?Sized,
+        {
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                T::cmp(self.deref(), other.deref())
+            }
+        }
+
+        impl<'a, T> Hash for $guard<'a, T>
+        where
+            T: Hash + ?Sized,
+        {
+            #[inline]
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                T::hash(self.deref(), state)
+            }
+        }
+    };
+}
+
+impl_guard_cmp!(Reference);
+impl_guard_cmp!(ReferenceMut);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    #[test]
+    fn compare_across_variants() {
+        let cell = RefCell::new(42);
+        let owned = OwnOrBorrow::own(42);
+        let borrowed = OwnOrBorrow::from(&cell);
+        assert!(owned == borrowed);
+        assert!(owned == 42);
+        assert!(OwnOrBorrow::own(1) < borrowed);
+        assert_eq!(
+            OwnOrBorrow::own(1).cmp(&OwnOrBorrow::own(2)),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn compare_guards() {
+        let cell = RefCell::new(42);
+        let owned = OwnOrBorrow::own(42);
+        let borrowed = OwnOrBorrow::from(&cell);
+        assert!(owned.borrow() == borrowed.borrow());
+        assert!(owned.borrow() == 42);
+        assert!(borrowed.borrow() > Reference::Borrowed(&1));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn hash_matches_inner_value() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash_of(value: &impl Hash) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let cell = RefCell::new("key");
+        assert_eq!(hash_of(&OwnOrBorrow::from(&cell)), hash_of(&"key"));
+        assert_eq!(hash_of(&OwnOrBorrow::own("key").borrow()), hash_of(&"key"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[allow(clippy::mutable_key_type)]
+    fn use_as_set_key() {
+        let mut set = std::collections::HashSet::new();
+        set.insert(OwnOrBorrow::own(1));
+        set.insert(OwnOrBorrow::from(RefCell::new(1)));
+        set.insert(OwnOrBorrow::own(2));
+        assert_eq!(set.len(), 2);
+    }
+}
@@ -0,0 +1,114 @@
+//! Interrupt-safe counterpart to [`OwnOrBorrow`](crate::OwnOrBorrow) built on
+//! `critical-section`.
+
+use crate::BorrowFault;
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+/// A type that provides either an owned or a borrowed [`critical_section::Mutex`] protected
+/// value.
+///
+/// All access happens through closures run inside a critical section, which makes the type
+/// suitable for state shared between interrupt handlers and the main loop.
+///
+/// ```
+/// use own_or_borrow::OwnOrCriticalSection;
+/// use core::cell::RefCell;
+/// use critical_section::Mutex;
+///
+/// static COUNTER: Mutex<RefCell<u32>> = Mutex::new(RefCell::new(0));
+///
+/// fn on_interrupt(counter: &OwnOrCriticalSection<'_, u32>) {
+///     counter.with(|value| *value += 1);
+/// }
+///
+/// let counter = OwnOrCriticalSection::from(&COUNTER);
+/// on_interrupt(&counter);
+/// assert_eq!(counter.with(|value| *value), 1);
+/// ```
+pub enum OwnOrCriticalSection<'a, T> {
+    /// An owned value.
+    Owned(Mutex<RefCell<T>>),
+    /// A borrowed value.
+    Borrowed(&'a Mutex<RefCell<T>>),
+}
+
+impl<'a, T> OwnOrCriticalSection<'a, T> {
+    /// Initializes a new instance that owns data.
+    pub const fn own(value: T) -> Self {
+        Self::Owned(Mutex::new(RefCell::new(value)))
+    }
+
+    /// Runs the closure with mutable access to the inner value inside a critical section.
+    ///
+    /// ## Panics
+    /// Panics if called re-entrantly from within the closure.
+    pub fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        critical_section::with(|cs| f(&mut self.cell(cs).borrow_mut()))
+    }
+
+    /// Runs the closure with mutable access to the inner value inside a critical section.
+    ///
+    /// Unlike [`with`](Self::with), this returns an error instead of panicking if called
+    /// re-entrantly from within the closure.
+    pub fn try_with<R, F>(&self, f: F) -> Result<R, BorrowFault>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        critical_section::with(|cs| {
+            let mut value = self.cell(cs).try_borrow_mut()?;
+            Ok(f(&mut value))
+        })
+    }
+
+    fn cell<'cs>(&'cs self, cs: critical_section::CriticalSection<'cs>) -> &'cs RefCell<T> {
+        match self {
+            OwnOrCriticalSection::Owned(mutex) => mutex.borrow(cs),
+            OwnOrCriticalSection::Borrowed(mutex) => mutex.borrow(cs),
+        }
+    }
+}
+
+impl<'a, T> From<Mutex<RefCell<T>>> for OwnOrCriticalSection<'a, T> {
+    #[inline]
+    fn from(value: Mutex<RefCell<T>>) -> Self {
+        Self::Owned(value)
+    }
+}
+
+impl<'a, T> From<&'a Mutex<RefCell<T>>> for OwnOrCriticalSection<'a, T> {
+    #[inline]
+    fn from(value: &'a Mutex<RefCell<T>>) -> Self {
+        Self::Borrowed(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_owned_type() {
+        let value = OwnOrCriticalSection::own(42);
+        value.with(|value| *value += 1);
+        assert_eq!(value.with(|value| *value), 43);
+    }
+
+    #[test]
+    fn with_borrowed_mutex() {
+        let mutex = Mutex::new(RefCell::new(42));
+        let value = OwnOrCriticalSection::from(&mutex);
+        value.with(|value| *value = 17);
+        assert_eq!(critical_section::with(|cs| *mutex.borrow_ref(cs)), 17);
+    }
+
+    #[test]
+    fn try_with_reports_reentrancy() {
+        let value = OwnOrCriticalSection::own(42);
+        let inner = value.with(|_| value.try_with(|_| ()));
+        assert!(matches!(inner, Err(BorrowFault::BorrowMut(_))));
+    }
+}
@@ -1,30 +1,223 @@
-use core::fmt::{Display, Formatter};
+use core::cell::{BorrowError, BorrowMutError};
+use core::fmt::{Debug, Display, Formatter};
 
 /// Errors from [`TryInto`] traits and related.
-#[derive(Debug)]
-pub enum TryIntoError {
+///
+/// The error carries the rejected value, so that it can be recovered with
+/// [`into_inner`](Self::into_inner), similar to `Rc::try_unwrap`.
+pub enum TryIntoError<T> {
     /// The type is not convertible.
-    NotConvertible,
+    NotConvertible(T),
+}
+
+impl<T> TryIntoError<T> {
+    /// Returns the value that could not be converted.
+    pub fn into_inner(self) -> T {
+        match self {
+            TryIntoError::NotConvertible(value) => value,
+        }
+    }
+}
+
+impl<T> Debug for TryIntoError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TryIntoError::NotConvertible(_) => {
+                f.debug_tuple("NotConvertible").finish_non_exhaustive()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<T> std::error::Error for TryIntoError<T> {}
+
+impl<T> Display for TryIntoError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TryIntoError::NotConvertible(_) => f.write_str("Invalid Type"),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl<T> defmt::Format for TryIntoError<T> {
+    fn format(&self, fmt: defmt::Formatter) {
+        match self {
+            TryIntoError::NotConvertible(_) => defmt::write!(fmt, "Invalid Type"),
+        }
+    }
+}
+
+/// Errors from [`OwnOrBorrow::try_borrow`](crate::OwnOrBorrow::try_borrow) and
+/// [`OwnOrBorrow::try_borrow_mut`](crate::OwnOrBorrow::try_borrow_mut).
+#[derive(Debug)]
+pub enum BorrowFault {
+    /// The value is currently mutably borrowed.
+    Borrow(BorrowError),
+    /// The value is currently borrowed.
+    BorrowMut(BorrowMutError),
+    /// The value is a plain shared reference and cannot be borrowed mutably.
+    Shared,
 }
 
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
-impl std::error::Error for TryIntoError {}
+impl std::error::Error for BorrowFault {}
 
-impl Display for TryIntoError {
+impl Display for BorrowFault {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
-            TryIntoError::NotConvertible => f.write_str("Invalid Type"),
+            BorrowFault::Borrow(error) => Display::fmt(error, f),
+            BorrowFault::BorrowMut(error) => Display::fmt(error, f),
+            BorrowFault::Shared => f.write_str("Cannot mutably borrow a shared reference"),
         }
     }
 }
 
 #[cfg(feature = "defmt")]
 #[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
-impl defmt::Format for TryIntoError {
+impl defmt::Format for BorrowFault {
     fn format(&self, fmt: defmt::Formatter) {
         match self {
-            TryIntoError::NotConvertible => defmt::write!(fmt, "Invalid Type"),
+            BorrowFault::Borrow(_) => defmt::write!(fmt, "Already mutably borrowed"),
+            BorrowFault::BorrowMut(_) => defmt::write!(fmt, "Already borrowed"),
+            BorrowFault::Shared => defmt::write!(fmt, "Cannot mutably borrow a shared reference"),
         }
     }
 }
+
+impl From<BorrowError> for BorrowFault {
+    #[inline]
+    fn from(value: BorrowError) -> Self {
+        BorrowFault::Borrow(value)
+    }
+}
+
+impl From<BorrowMutError> for BorrowFault {
+    #[inline]
+    fn from(value: BorrowMutError) -> Self {
+        BorrowFault::BorrowMut(value)
+    }
+}
+
+/// Errors from `OwnOrLock` and `OwnOrSpin` accesses.
+///
+/// Spin locks cannot be poisoned and only fail with [`LockError::WouldBlock`].
+#[cfg(any(feature = "std", feature = "spin"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "spin"))))]
+#[derive(Debug)]
+pub enum LockError {
+    /// The lock was poisoned by a thread panicking while holding it.
+    Poisoned,
+    /// The lock could not be acquired without blocking.
+    WouldBlock,
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for LockError {}
+
+#[cfg(any(feature = "std", feature = "spin"))]
+impl Display for LockError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LockError::Poisoned => f.write_str("Lock poisoned"),
+            LockError::WouldBlock => f.write_str("Lock would block"),
+        }
+    }
+}
+
+#[cfg(all(any(feature = "std", feature = "spin"), feature = "defmt"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(any(feature = "std", feature = "spin"), feature = "defmt")))
+)]
+impl defmt::Format for LockError {
+    fn format(&self, fmt: defmt::Formatter) {
+        match self {
+            LockError::Poisoned => defmt::write!(fmt, "Lock poisoned"),
+            LockError::WouldBlock => defmt::write!(fmt, "Lock would block"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<G> From<std::sync::PoisonError<G>> for LockError {
+    #[inline]
+    fn from(_value: std::sync::PoisonError<G>) -> Self {
+        LockError::Poisoned
+    }
+}
+
+#[cfg(feature = "std")]
+impl<G> From<std::sync::TryLockError<G>> for LockError {
+    #[inline]
+    fn from(value: std::sync::TryLockError<G>) -> Self {
+        match value {
+            std::sync::TryLockError::Poisoned(_) => LockError::Poisoned,
+            std::sync::TryLockError::WouldBlock => LockError::WouldBlock,
+        }
+    }
+}
+
+/// Errors from [`OwnOrAtomicBorrow::try_borrow`](crate::OwnOrAtomicBorrow::try_borrow) and
+/// [`OwnOrAtomicBorrow::try_borrow_mut`](crate::OwnOrAtomicBorrow::try_borrow_mut).
+#[cfg(feature = "atomic_refcell")]
+#[cfg_attr(docsrs, doc(cfg(feature = "atomic_refcell")))]
+#[derive(Debug)]
+pub enum AtomicBorrowFault {
+    /// The value is currently mutably borrowed.
+    Borrow(atomic_refcell::BorrowError),
+    /// The value is currently borrowed.
+    BorrowMut(atomic_refcell::BorrowMutError),
+    /// The value is a plain shared reference and cannot be borrowed mutably.
+    Shared,
+}
+
+#[cfg(all(feature = "atomic_refcell", feature = "std"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "atomic_refcell", feature = "std"))))]
+impl std::error::Error for AtomicBorrowFault {}
+
+#[cfg(feature = "atomic_refcell")]
+impl Display for AtomicBorrowFault {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AtomicBorrowFault::Borrow(error) => Display::fmt(error, f),
+            AtomicBorrowFault::BorrowMut(error) => Display::fmt(error, f),
+            AtomicBorrowFault::Shared => f.write_str("Cannot mutably borrow a shared reference"),
+        }
+    }
+}
+
+#[cfg(all(feature = "atomic_refcell", feature = "defmt"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "atomic_refcell", feature = "defmt"))))]
+impl defmt::Format for AtomicBorrowFault {
+    fn format(&self, fmt: defmt::Formatter) {
+        match self {
+            AtomicBorrowFault::Borrow(_) => defmt::write!(fmt, "Already mutably borrowed"),
+            AtomicBorrowFault::BorrowMut(_) => defmt::write!(fmt, "Already borrowed"),
+            AtomicBorrowFault::Shared => {
+                defmt::write!(fmt, "Cannot mutably borrow a shared reference")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "atomic_refcell")]
+impl From<atomic_refcell::BorrowError> for AtomicBorrowFault {
+    #[inline]
+    fn from(value: atomic_refcell::BorrowError) -> Self {
+        AtomicBorrowFault::Borrow(value)
+    }
+}
+
+#[cfg(feature = "atomic_refcell")]
+impl From<atomic_refcell::BorrowMutError> for AtomicBorrowFault {
+    #[inline]
+    fn from(value: atomic_refcell::BorrowMutError) -> Self {
+        AtomicBorrowFault::BorrowMut(value)
+    }
+}
@@ -0,0 +1,175 @@
+//! Counterpart to [`OwnOrBorrow`] for exclusive access.
+
+use crate::{OwnOrBorrow, Reference, ReferenceMut};
+
+/// A type that provides either an owned value or a plain mutable reference to a value.
+///
+/// Unlike [`OwnOrBorrow`], there is no [`RefCell`](core::cell::RefCell) variant, so borrowing
+/// is infallible and involves no run-time borrow checks. Borrowing returns the regular
+/// [`Reference`] and [`ReferenceMut`] guards.
+///
+/// ```
+/// use own_or_borrow::OwnOrBorrowMut;
+///
+/// fn append(buffer: &mut OwnOrBorrowMut<'_, Vec<u8>>) {
+///     buffer.borrow_mut().push(42);
+/// }
+///
+/// let mut data = Vec::new();
+/// append(&mut OwnOrBorrowMut::from(&mut data));
+/// assert_eq!(data, [42]);
+///
+/// let mut owned = OwnOrBorrowMut::own(Vec::new());
+/// append(&mut owned);
+/// assert_eq!(*owned.borrow(), [42]);
+/// ```
+pub enum OwnOrBorrowMut<'a, T> {
+    /// An owned value.
+    Owned(T),
+    /// A plain mutable reference.
+    Borrowed(&'a mut T),
+}
+
+impl<'a, T> OwnOrBorrowMut<'a, T> {
+    /// Initializes a new instance that owns data.
+    pub const fn own(value: T) -> Self {
+        Self::Owned(value)
+    }
+
+    /// Initializes a new instance from a plain mutable reference.
+    pub const fn borrowed_mut(value: &'a mut T) -> Self {
+        Self::Borrowed(value)
+    }
+
+    /// Returns `true` if the value is owned.
+    pub fn is_owned(&self) -> bool {
+        matches!(self, OwnOrBorrowMut::Owned(_))
+    }
+
+    /// Borrows the inner value.
+    #[inline]
+    pub fn borrow(&self) -> Reference<'_, T> {
+        Reference::Borrowed(self.get())
+    }
+
+    /// Borrows the inner value mutably. This never fails.
+    #[inline]
+    pub fn borrow_mut(&mut self) -> ReferenceMut<'_, T> {
+        ReferenceMut::Borrowed(self.get_mut())
+    }
+
+    /// Returns a shared reference to the inner value.
+    #[inline]
+    pub fn get(&self) -> &T {
+        match self {
+            OwnOrBorrowMut::Owned(value) => value,
+            OwnOrBorrowMut::Borrowed(value) => value,
+        }
+    }
+
+    /// Returns a mutable reference to the inner value.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        match self {
+            OwnOrBorrowMut::Owned(value) => value,
+            OwnOrBorrowMut::Borrowed(value) => value,
+        }
+    }
+
+    /// Replaces the inner value, returning the old value.
+    pub fn replace(&mut self, value: T) -> T {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Extracts the owned value, or returns the wrapper unchanged if it is borrowed.
+    pub fn try_unwrap(self) -> Result<T, Self> {
+        match self {
+            OwnOrBorrowMut::Owned(value) => Ok(value),
+            other => Err(other),
+        }
+    }
+}
+
+impl<'a, T> OwnOrBorrowMut<'a, T>
+where
+    T: Clone,
+{
+    /// Extracts the owned value, cloning it if it is borrowed.
+    pub fn into_owned(self) -> T {
+        match self {
+            OwnOrBorrowMut::Owned(value) => value,
+            OwnOrBorrowMut::Borrowed(value) => value.clone(),
+        }
+    }
+}
+
+impl<'a, T> From<&'a mut T> for OwnOrBorrowMut<'a, T> {
+    #[inline]
+    fn from(value: &'a mut T) -> Self {
+        Self::Borrowed(value)
+    }
+}
+
+impl<'a, T> From<OwnOrBorrowMut<'a, T>> for OwnOrBorrow<'a, T> {
+    /// Converts into the [`OwnOrBorrow::Owned`] or [`OwnOrBorrow::BorrowedMut`] variant.
+    #[inline]
+    fn from(value: OwnOrBorrowMut<'a, T>) -> Self {
+        match value {
+            OwnOrBorrowMut::Owned(value) => OwnOrBorrow::Owned(value),
+            OwnOrBorrowMut::Borrowed(value) => OwnOrBorrow::BorrowedMut(value),
+        }
+    }
+}
+
+impl<'a, T: Default> Default for OwnOrBorrowMut<'a, T> {
+    fn default() -> Self {
+        Self::Owned(T::default())
+    }
+}
+
+impl<'a, T> core::fmt::Debug for OwnOrBorrowMut<'a, T>
+where
+    T: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.get(), f)
+    }
+}
+
+impl<'a, T> core::fmt::Display for OwnOrBorrowMut<'a, T>
+where
+    T: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self.get(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrow_mut_owned_and_borrowed() {
+        let mut value = OwnOrBorrowMut::own(1);
+        *value.borrow_mut() += 1;
+        assert_eq!(*value.borrow(), 2);
+        assert_eq!(value.try_unwrap().ok(), Some(2));
+
+        let mut data = 1;
+        let mut value = OwnOrBorrowMut::from(&mut data);
+        assert!(!value.is_owned());
+        assert_eq!(value.replace(5), 1);
+        assert_eq!(value.into_owned(), 5);
+        assert_eq!(data, 5);
+    }
+
+    #[test]
+    fn into_own_or_borrow() {
+        let mut data = 1;
+        let mut value = OwnOrBorrow::from(OwnOrBorrowMut::from(&mut data));
+        assert!(matches!(value, OwnOrBorrow::BorrowedMut(_)));
+        *value.borrow_mut() += 1;
+        assert_eq!(data, 2);
+    }
+}
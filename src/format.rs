@@ -0,0 +1,425 @@
+//! Deferred formatting via [`defmt`].
+//!
+//! Guards and wrappers include their variant name in the output, e.g. `RefCellRef(42)`, so
+//! logs show where the data lives.
+
+use crate::audit::{self, Source, Wrapper};
+#[cfg(feature = "alloc")]
+use crate::OwnOrWeak;
+#[cfg(feature = "tokio")]
+use crate::{AsyncReference, AsyncReferenceMut};
+#[cfg(feature = "atomic_refcell")]
+use crate::{AtomicReference, AtomicReferenceMut, OwnOrAtomicBorrow};
+use crate::{
+    LazyOwnOrBorrow, OwnOrBorrow, OwnOrBorrowMut, OwnOrBorrowUnsized, OwnOrCell, Reference,
+    ReferenceMut,
+};
+#[cfg(feature = "std")]
+use crate::{LockError, LockReference, LockReferenceMut, OwnOrLock};
+#[cfg(feature = "spin")]
+use crate::{OwnOrSpin, SpinReference, SpinReferenceMut};
+use core::ops::Deref;
+use defmt::{Format, Formatter};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl<'a, T> Format for Reference<'a, T>
+where
+    T: Format + ?Sized,
+{
+    fn format(&self, fmt: Formatter) {
+        let data = self.deref();
+        match self {
+            Reference::Borrowed(_) => defmt::write!(fmt, "Borrowed({})", data),
+            Reference::RefCell(_) => defmt::write!(fmt, "RefCell({})", data),
+            Reference::RefCellRef(..) => defmt::write!(fmt, "RefCellRef({})", data),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl<'a, T> Format for ReferenceMut<'a, T>
+where
+    T: Format + ?Sized,
+{
+    fn format(&self, fmt: Formatter) {
+        let data = self.deref();
+        match self {
+            ReferenceMut::Borrowed(_) => defmt::write!(fmt, "Borrowed({})", data),
+            ReferenceMut::RefCell(_) => defmt::write!(fmt, "RefCell({})", data),
+            ReferenceMut::RefCellRef(..) => defmt::write!(fmt, "RefCellRef({})", data),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "defmt", feature = "std"))))]
+impl<'a, T> Format for LockReference<'a, T>
+where
+    T: Format,
+{
+    fn format(&self, fmt: Formatter) {
+        let data = self.deref();
+        match self {
+            LockReference::Borrowed(_) => defmt::write!(fmt, "Borrowed({})", data),
+            LockReference::RwLock(_) => defmt::write!(fmt, "RwLock({})", data),
+            LockReference::Mutex(_) => defmt::write!(fmt, "Mutex({})", data),
+            #[cfg(feature = "parking_lot")]
+            LockReference::ParkingLotRwLock(_) => {
+                defmt::write!(fmt, "ParkingLotRwLock({})", data)
+            }
+            #[cfg(feature = "parking_lot")]
+            LockReference::ParkingLotMutex(_) => {
+                defmt::write!(fmt, "ParkingLotMutex({})", data)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "defmt", feature = "std"))))]
+impl<'a, T> Format for LockReferenceMut<'a, T>
+where
+    T: Format,
+{
+    fn format(&self, fmt: Formatter) {
+        let data = self.deref();
+        match self {
+            LockReferenceMut::Borrowed(_) => defmt::write!(fmt, "Borrowed({})", data),
+            LockReferenceMut::RwLock(_) => defmt::write!(fmt, "RwLock({})", data),
+            LockReferenceMut::Mutex(_) => defmt::write!(fmt, "Mutex({})", data),
+            #[cfg(feature = "parking_lot")]
+            LockReferenceMut::ParkingLotRwLock(_) => {
+                defmt::write!(fmt, "ParkingLotRwLock({})", data)
+            }
+            #[cfg(feature = "parking_lot")]
+            LockReferenceMut::ParkingLotMutex(_) => {
+                defmt::write!(fmt, "ParkingLotMutex({})", data)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "defmt", feature = "tokio"))))]
+impl<'a, T> Format for AsyncReference<'a, T>
+where
+    T: Format,
+{
+    fn format(&self, fmt: Formatter) {
+        let data = self.deref();
+        match self {
+            AsyncReference::Borrowed(_) => defmt::write!(fmt, "Borrowed({})", data),
+            AsyncReference::RwLock(_) => defmt::write!(fmt, "RwLock({})", data),
+            AsyncReference::Mutex(_) => defmt::write!(fmt, "Mutex({})", data),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "defmt", feature = "tokio"))))]
+impl<'a, T> Format for AsyncReferenceMut<'a, T>
+where
+    T: Format,
+{
+    fn format(&self, fmt: Formatter) {
+        let data = self.deref();
+        match self {
+            AsyncReferenceMut::Borrowed(_) => defmt::write!(fmt, "Borrowed({})", data),
+            AsyncReferenceMut::RwLock(_) => defmt::write!(fmt, "RwLock({})", data),
+            AsyncReferenceMut::Mutex(_) => defmt::write!(fmt, "Mutex({})", data),
+        }
+    }
+}
+
+#[cfg(feature = "atomic_refcell")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "defmt", feature = "atomic_refcell"))))]
+impl<'a, T> Format for AtomicReference<'a, T>
+where
+    T: Format + ?Sized,
+{
+    fn format(&self, fmt: Formatter) {
+        let data = self.deref();
+        match self {
+            AtomicReference::Borrowed(_) => defmt::write!(fmt, "Borrowed({})", data),
+            AtomicReference::AtomicRefCell(_) => {
+                defmt::write!(fmt, "AtomicRefCell({})", data)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "atomic_refcell")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "defmt", feature = "atomic_refcell"))))]
+impl<'a, T> Format for AtomicReferenceMut<'a, T>
+where
+    T: Format + ?Sized,
+{
+    fn format(&self, fmt: Formatter) {
+        let data = self.deref();
+        match self {
+            AtomicReferenceMut::Borrowed(_) => {
+                defmt::write!(fmt, "Borrowed({})", data)
+            }
+            AtomicReferenceMut::AtomicRefCell(_) => {
+                defmt::write!(fmt, "AtomicRefCell({})", data)
+            }
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl<'a, T> Format for OwnOrBorrow<'a, T>
+where
+    T: Format,
+{
+    /// Prints `<mutably borrowed>` if the value is currently mutably borrowed.
+    fn format(&self, fmt: Formatter) {
+        let Ok(data) = self.try_borrow() else {
+            return defmt::write!(fmt, "<mutably borrowed>");
+        };
+        let data = data.as_ref();
+        match self {
+            OwnOrBorrow::Owned(_) => defmt::write!(fmt, "Owned({})", data),
+            OwnOrBorrow::RefCell(_) => defmt::write!(fmt, "RefCell({})", data),
+            OwnOrBorrow::RefCellRef(_) => defmt::write!(fmt, "RefCellRef({})", data),
+            OwnOrBorrow::Borrowed(_) => defmt::write!(fmt, "Borrowed({})", data),
+            OwnOrBorrow::BorrowedMut(_) => defmt::write!(fmt, "BorrowedMut({})", data),
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(_) => defmt::write!(fmt, "RefCellRc({})", data),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl<'a, T> Format for OwnOrBorrowUnsized<'a, T>
+where
+    T: Format + ?Sized,
+{
+    /// Prints `<mutably borrowed>` if the value is currently mutably borrowed.
+    fn format(&self, fmt: Formatter) {
+        let Ok(data) = self.try_borrow() else {
+            return defmt::write!(fmt, "<mutably borrowed>");
+        };
+        let data = data.as_ref();
+        match self {
+            OwnOrBorrowUnsized::RefCellRef(_) => defmt::write!(fmt, "RefCellRef({})", data),
+            OwnOrBorrowUnsized::Borrowed(_) => defmt::write!(fmt, "Borrowed({})", data),
+            OwnOrBorrowUnsized::BorrowedMut(_) => defmt::write!(fmt, "BorrowedMut({})", data),
+            #[cfg(feature = "alloc")]
+            OwnOrBorrowUnsized::Boxed(_) => defmt::write!(fmt, "Boxed({})", data),
+            #[cfg(feature = "alloc")]
+            OwnOrBorrowUnsized::RefCellRc(_) => defmt::write!(fmt, "RefCellRc({})", data),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl<'a, T, F> Format for LazyOwnOrBorrow<'a, T, F>
+where
+    T: Format,
+    F: FnOnce() -> T,
+{
+    /// Like the `Debug` implementation, this does not compute a lazy value and prints
+    /// `Lazy(<uninit>)` instead, and prints `RefCellRef(<mutably borrowed>)` if the value is
+    /// currently mutably borrowed.
+    fn format(&self, fmt: Formatter) {
+        match self {
+            LazyOwnOrBorrow::Owned(value) => defmt::write!(fmt, "Owned({})", value),
+            LazyOwnOrBorrow::Lazy(value, _) => match value.get() {
+                Some(value) => defmt::write!(fmt, "Lazy({})", value),
+                None => defmt::write!(fmt, "Lazy(<uninit>)"),
+            },
+            LazyOwnOrBorrow::RefCellRef(ref_cell) => {
+                match audit::try_borrow(Wrapper::LazyOwnOrBorrow, ref_cell, Source::RefCellRef) {
+                    Ok(data) => defmt::write!(fmt, "RefCellRef({})", data.deref()),
+                    Err(_) => defmt::write!(fmt, "RefCellRef(<mutably borrowed>)"),
+                }
+            }
+            LazyOwnOrBorrow::Borrowed(value) => defmt::write!(fmt, "Borrowed({})", value),
+            LazyOwnOrBorrow::BorrowedMut(value) => {
+                defmt::write!(fmt, "BorrowedMut({})", value.deref())
+            }
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl<'a, T> Format for OwnOrCell<'a, T>
+where
+    T: Copy + Format,
+{
+    fn format(&self, fmt: Formatter) {
+        let data = self.get();
+        match self {
+            OwnOrCell::Cell(_) => defmt::write!(fmt, "Cell({})", data),
+            OwnOrCell::CellRef(_) => defmt::write!(fmt, "CellRef({})", data),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl<'a, T> Format for OwnOrBorrowMut<'a, T>
+where
+    T: Format,
+{
+    fn format(&self, fmt: Formatter) {
+        match self {
+            OwnOrBorrowMut::Owned(data) => defmt::write!(fmt, "Owned({})", data),
+            OwnOrBorrowMut::Borrowed(data) => defmt::write!(fmt, "Borrowed({})", data),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "defmt", feature = "alloc"))))]
+impl<'a, T> Format for OwnOrWeak<'a, T>
+where
+    T: Format,
+{
+    /// Prints `RefCellWeak(<dropped>)` if the value was dropped, or `<mutably borrowed>` if
+    /// it is currently mutably borrowed.
+    fn format(&self, fmt: Formatter) {
+        let Some(value) = self.upgrade() else {
+            return defmt::write!(fmt, "RefCellWeak(<dropped>)");
+        };
+        let Ok(data) = value.try_borrow() else {
+            return defmt::write!(fmt, "<mutably borrowed>");
+        };
+        let data = data.as_ref();
+        match self {
+            OwnOrWeak::Owned(_) => defmt::write!(fmt, "Owned({})", data),
+            OwnOrWeak::RefCellRef(_) => defmt::write!(fmt, "RefCellRef({})", data),
+            OwnOrWeak::RefCellRc(_) => defmt::write!(fmt, "RefCellRc({})", data),
+            OwnOrWeak::RefCellWeak(_) => defmt::write!(fmt, "RefCellWeak({})", data),
+        }
+    }
+}
+
+#[cfg(feature = "atomic_refcell")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "defmt", feature = "atomic_refcell"))))]
+impl<'a, T> Format for OwnOrAtomicBorrow<'a, T>
+where
+    T: Format,
+{
+    /// Prints `<mutably borrowed>` if the value is currently mutably borrowed.
+    fn format(&self, fmt: Formatter) {
+        let Ok(data) = self.try_borrow() else {
+            return defmt::write!(fmt, "<mutably borrowed>");
+        };
+        let data = data.as_ref();
+        match self {
+            OwnOrAtomicBorrow::Owned(_) => defmt::write!(fmt, "Owned({})", data),
+            OwnOrAtomicBorrow::AtomicRefCell(_) => defmt::write!(fmt, "AtomicRefCell({})", data),
+            OwnOrAtomicBorrow::AtomicRefCellRef(_) => {
+                defmt::write!(fmt, "AtomicRefCellRef({})", data)
+            }
+            OwnOrAtomicBorrow::Borrowed(_) => defmt::write!(fmt, "Borrowed({})", data),
+            OwnOrAtomicBorrow::BorrowedMut(_) => defmt::write!(fmt, "BorrowedMut({})", data),
+            #[cfg(feature = "alloc")]
+            OwnOrAtomicBorrow::AtomicRefCellArc(_) => {
+                defmt::write!(fmt, "AtomicRefCellArc({})", data)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "defmt", feature = "std"))))]
+impl<'a, T> Format for OwnOrLock<'a, T>
+where
+    T: Format,
+{
+    /// Prints `<locked>` if the lock is currently held exclusively, or `<poisoned>` if it is
+    /// poisoned.
+    fn format(&self, fmt: Formatter) {
+        let data = match self.try_read() {
+            Ok(data) => data,
+            Err(LockError::WouldBlock) => return defmt::write!(fmt, "<locked>"),
+            Err(LockError::Poisoned) => return defmt::write!(fmt, "<poisoned>"),
+        };
+        let data = data.as_ref();
+        match self {
+            OwnOrLock::Owned(_) => defmt::write!(fmt, "Owned({})", data),
+            OwnOrLock::RwLock(_) => defmt::write!(fmt, "RwLock({})", data),
+            OwnOrLock::RwLockRef(_) => defmt::write!(fmt, "RwLockRef({})", data),
+            OwnOrLock::RwLockArc(_) => defmt::write!(fmt, "RwLockArc({})", data),
+            OwnOrLock::Mutex(_) => defmt::write!(fmt, "Mutex({})", data),
+            OwnOrLock::MutexRef(_) => defmt::write!(fmt, "MutexRef({})", data),
+            OwnOrLock::MutexArc(_) => defmt::write!(fmt, "MutexArc({})", data),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLock(_) => defmt::write!(fmt, "ParkingLotRwLock({})", data),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLockRef(_) => {
+                defmt::write!(fmt, "ParkingLotRwLockRef({})", data)
+            }
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLockArc(_) => {
+                defmt::write!(fmt, "ParkingLotRwLockArc({})", data)
+            }
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutex(_) => defmt::write!(fmt, "ParkingLotMutex({})", data),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutexRef(_) => defmt::write!(fmt, "ParkingLotMutexRef({})", data),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutexArc(_) => defmt::write!(fmt, "ParkingLotMutexArc({})", data),
+        }
+    }
+}
+
+#[cfg(feature = "spin")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "defmt", feature = "spin"))))]
+impl<'a, T> Format for SpinReference<'a, T>
+where
+    T: Format,
+{
+    fn format(&self, fmt: Formatter) {
+        let data = self.deref();
+        match self {
+            SpinReference::Borrowed(_) => defmt::write!(fmt, "Borrowed({})", data),
+            SpinReference::RwLock(_) => defmt::write!(fmt, "RwLock({})", data),
+            SpinReference::Mutex(_) => defmt::write!(fmt, "Mutex({})", data),
+        }
+    }
+}
+
+#[cfg(feature = "spin")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "defmt", feature = "spin"))))]
+impl<'a, T> Format for SpinReferenceMut<'a, T>
+where
+    T: Format,
+{
+    fn format(&self, fmt: Formatter) {
+        let data = self.deref();
+        match self {
+            SpinReferenceMut::Borrowed(_) => defmt::write!(fmt, "Borrowed({})", data),
+            SpinReferenceMut::RwLock(_) => defmt::write!(fmt, "RwLock({})", data),
+            SpinReferenceMut::Mutex(_) => defmt::write!(fmt, "Mutex({})", data),
+        }
+    }
+}
+
+#[cfg(feature = "spin")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "defmt", feature = "spin"))))]
+impl<'a, T> Format for OwnOrSpin<'a, T>
+where
+    T: Format,
+{
+    /// Prints `<locked>` if the lock is currently held exclusively.
+    fn format(&self, fmt: Formatter) {
+        let Some(data) = self.try_read() else {
+            return defmt::write!(fmt, "<locked>");
+        };
+        let data = data.as_ref();
+        match self {
+            OwnOrSpin::Owned(_) => defmt::write!(fmt, "Owned({})", data),
+            OwnOrSpin::RwLock(_) => defmt::write!(fmt, "RwLock({})", data),
+            OwnOrSpin::RwLockRef(_) => defmt::write!(fmt, "RwLockRef({})", data),
+            #[cfg(feature = "alloc")]
+            OwnOrSpin::RwLockArc(_) => defmt::write!(fmt, "RwLockArc({})", data),
+            OwnOrSpin::Mutex(_) => defmt::write!(fmt, "Mutex({})", data),
+            OwnOrSpin::MutexRef(_) => defmt::write!(fmt, "MutexRef({})", data),
+            #[cfg(feature = "alloc")]
+            OwnOrSpin::MutexArc(_) => defmt::write!(fmt, "MutexArc({})", data),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! `arbitrary` support.
+
+use crate::OwnOrBorrow;
+use arbitrary::{Arbitrary, Result, Unstructured};
+use core::cell::RefCell;
+
+/// Generates either an [`OwnOrBorrow::Owned`] or an [`OwnOrBorrow::RefCell`] value, so
+/// fuzzers exercise both storage paths.
+///
+/// Borrowed variants cannot be generated, as there is nothing to borrow from.
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+impl<'u, 'a, T> Arbitrary<'u> for OwnOrBorrow<'a, T>
+where
+    T: Arbitrary<'u>,
+{
+    fn arbitrary(u: &mut Unstructured<'u>) -> Result<Self> {
+        let value = T::arbitrary(u)?;
+        Ok(if u.arbitrary()? {
+            OwnOrBorrow::Owned(value)
+        } else {
+            OwnOrBorrow::RefCell(RefCell::new(value))
+        })
+    }
+
+    fn arbitrary_take_rest(mut u: Unstructured<'u>) -> Result<Self> {
+        let owned = u.arbitrary()?;
+        let value = T::arbitrary_take_rest(u)?;
+        Ok(if owned {
+            OwnOrBorrow::Owned(value)
+        } else {
+            OwnOrBorrow::RefCell(RefCell::new(value))
+        })
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and(T::size_hint(depth), <bool as Arbitrary>::size_hint(depth))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_both_variants() {
+        let owned = OwnOrBorrow::<u8>::arbitrary(&mut Unstructured::new(&[42, 1]))
+            .expect("failed to generate");
+        assert!(matches!(owned, OwnOrBorrow::Owned(42)));
+
+        let cell = OwnOrBorrow::<u8>::arbitrary(&mut Unstructured::new(&[42, 0]))
+            .expect("failed to generate");
+        assert!(matches!(cell, OwnOrBorrow::RefCell(_)));
+        assert_eq!(*cell.borrow(), 42);
+    }
+}
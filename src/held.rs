@@ -0,0 +1,142 @@
+//! Lifetime-free access to owned values.
+
+use crate::{OwnOrBorrow, Owned};
+use core::ops::{Deref, DerefMut};
+
+/// A self-contained view of a value taken out of an [`OwnOrBorrow`], as returned by
+/// [`OwnOrBorrow::hold`].
+///
+/// The guard owns the value, so it carries no lifetime and can be stored in struct fields
+/// without making the struct generic over one. Since the guard is the only owner, access
+/// needs no run-time borrow checks.
+///
+/// ```
+/// use own_or_borrow::{HeldReference, OwnOrBorrow};
+/// use core::cell::RefCell;
+///
+/// struct Cache {
+///     entries: HeldReference<Vec<u32>>,
+/// }
+///
+/// fn load(value: OwnOrBorrow<'_, Vec<u32>>) -> Option<Cache> {
+///     let entries = value.hold().ok()?;
+///     Some(Cache { entries })
+/// }
+///
+/// let mut cache = load(OwnOrBorrow::from(RefCell::new(vec![1, 2, 3]))).expect("cell is owned");
+/// cache.entries.push(4);
+/// assert_eq!(cache.entries.len(), 4);
+///
+/// let borrowed = RefCell::new(vec![1, 2, 3]);
+/// assert!(load(OwnOrBorrow::from(&borrowed)).is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HeldReference<T>(T);
+
+impl<'a, T> OwnOrBorrow<'a, T> {
+    /// Moves an owned value into a [`HeldReference`], erasing the lifetime `'a`.
+    ///
+    /// This succeeds for owned values, owned [`RefCell`](core::cell::RefCell) instances and
+    /// uniquely owned `OwnOrBorrow::RefCellRc` values, like [`try_unwrap`](Self::try_unwrap).
+    /// Borrowed values cannot outlive `'a`, so the wrapper is returned unchanged.
+    pub fn hold(self) -> Result<HeldReference<T>, Self> {
+        self.try_unwrap().map(HeldReference)
+    }
+}
+
+impl<T> HeldReference<T> {
+    /// Returns the held value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for HeldReference<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for HeldReference<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> AsRef<T> for HeldReference<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> AsMut<T> for HeldReference<T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> core::borrow::Borrow<T> for HeldReference<T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> core::borrow::BorrowMut<T> for HeldReference<T> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> From<HeldReference<T>> for Owned<T> {
+    /// Converts the held value back into an [`OwnOrBorrow::Owned`] wrapper.
+    #[inline]
+    fn from(value: HeldReference<T>) -> Self {
+        OwnOrBorrow::Owned(value.0)
+    }
+}
+
+impl<T> core::fmt::Display for HeldReference<T>
+where
+    T: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    #[test]
+    fn hold_owned_variants() {
+        let mut held = OwnOrBorrow::own(41).hold().expect("value is owned");
+        *held += 1;
+        assert_eq!(*held, 42);
+
+        let held = OwnOrBorrow::from(RefCell::new(42))
+            .hold()
+            .expect("cell is owned");
+        assert_eq!(Owned::from(held).try_unwrap().ok(), Some(42));
+    }
+
+    #[test]
+    fn hold_borrowed_value_fails() {
+        let cell = RefCell::new(42);
+        let value = OwnOrBorrow::from(&cell)
+            .hold()
+            .expect_err("value is borrowed");
+        assert!(matches!(value, OwnOrBorrow::RefCellRef(_)));
+    }
+}
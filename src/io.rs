@@ -0,0 +1,362 @@
+//! I/O trait forwarding for the guard types.
+
+#[cfg(feature = "std")]
+mod std_io {
+    use crate::ReferenceMut;
+    use std::io::{BufRead, IoSlice, IoSliceMut, Read, Result, Seek, SeekFrom, Write};
+    use std::ops::DerefMut;
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    impl<'a, T> Read for ReferenceMut<'a, T>
+    where
+        T: Read + ?Sized,
+    {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            self.deref_mut().read(buf)
+        }
+
+        #[inline]
+        fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+            self.deref_mut().read_vectored(bufs)
+        }
+
+        #[inline]
+        fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+            self.deref_mut().read_to_end(buf)
+        }
+
+        #[inline]
+        fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+            self.deref_mut().read_to_string(buf)
+        }
+
+        #[inline]
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+            self.deref_mut().read_exact(buf)
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    impl<'a, T> BufRead for ReferenceMut<'a, T>
+    where
+        T: BufRead + ?Sized,
+    {
+        #[inline]
+        fn fill_buf(&mut self) -> Result<&[u8]> {
+            self.deref_mut().fill_buf()
+        }
+
+        #[inline]
+        fn consume(&mut self, amt: usize) {
+            self.deref_mut().consume(amt)
+        }
+
+        #[inline]
+        fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
+            self.deref_mut().read_until(byte, buf)
+        }
+
+        #[inline]
+        fn read_line(&mut self, buf: &mut String) -> Result<usize> {
+            self.deref_mut().read_line(buf)
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    impl<'a, T> Write for ReferenceMut<'a, T>
+    where
+        T: Write + ?Sized,
+    {
+        #[inline]
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.deref_mut().write(buf)
+        }
+
+        #[inline]
+        fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+            self.deref_mut().write_vectored(bufs)
+        }
+
+        #[inline]
+        fn flush(&mut self) -> Result<()> {
+            self.deref_mut().flush()
+        }
+
+        #[inline]
+        fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+            self.deref_mut().write_all(buf)
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    impl<'a, T> Seek for ReferenceMut<'a, T>
+    where
+        T: Seek + ?Sized,
+    {
+        #[inline]
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+            self.deref_mut().seek(pos)
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+mod embedded {
+    use crate::ReferenceMut;
+    use core::ops::DerefMut;
+    use embedded_io::{
+        BufRead, ErrorType, Read, ReadExactError, ReadReady, Seek, SeekFrom, Write, WriteReady,
+    };
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-io")))]
+    impl<'a, T> ErrorType for ReferenceMut<'a, T>
+    where
+        T: ErrorType + ?Sized,
+    {
+        type Error = T::Error;
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-io")))]
+    impl<'a, T> Read for ReferenceMut<'a, T>
+    where
+        T: Read + ?Sized,
+    {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            self.deref_mut().read(buf)
+        }
+
+        #[inline]
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), ReadExactError<Self::Error>> {
+            self.deref_mut().read_exact(buf)
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-io")))]
+    impl<'a, T> BufRead for ReferenceMut<'a, T>
+    where
+        T: BufRead + ?Sized,
+    {
+        #[inline]
+        fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+            self.deref_mut().fill_buf()
+        }
+
+        #[inline]
+        fn consume(&mut self, amt: usize) {
+            self.deref_mut().consume(amt)
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-io")))]
+    impl<'a, T> Write for ReferenceMut<'a, T>
+    where
+        T: Write + ?Sized,
+    {
+        #[inline]
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.deref_mut().write(buf)
+        }
+
+        #[inline]
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            self.deref_mut().flush()
+        }
+
+        #[inline]
+        fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+            self.deref_mut().write_all(buf)
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-io")))]
+    impl<'a, T> Seek for ReferenceMut<'a, T>
+    where
+        T: Seek + ?Sized,
+    {
+        #[inline]
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error> {
+            self.deref_mut().seek(pos)
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-io")))]
+    impl<'a, T> ReadReady for ReferenceMut<'a, T>
+    where
+        T: ReadReady + ?Sized,
+    {
+        #[inline]
+        fn read_ready(&mut self) -> Result<bool, Self::Error> {
+            self.deref_mut().read_ready()
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-io")))]
+    impl<'a, T> WriteReady for ReferenceMut<'a, T>
+    where
+        T: WriteReady + ?Sized,
+    {
+        #[inline]
+        fn write_ready(&mut self) -> Result<bool, Self::Error> {
+            self.deref_mut().write_ready()
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+mod embedded_async {
+    use crate::ReferenceMut;
+    use core::ops::DerefMut;
+    use embedded_io_async::{BufRead, Read, ReadExactError, Seek, SeekFrom, Write};
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-io-async")))]
+    impl<'a, T> Read for ReferenceMut<'a, T>
+    where
+        T: Read + ?Sized,
+    {
+        #[inline]
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            self.deref_mut().read(buf).await
+        }
+
+        #[inline]
+        async fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), ReadExactError<Self::Error>> {
+            self.deref_mut().read_exact(buf).await
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-io-async")))]
+    impl<'a, T> BufRead for ReferenceMut<'a, T>
+    where
+        T: BufRead + ?Sized,
+    {
+        #[inline]
+        async fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+            self.deref_mut().fill_buf().await
+        }
+
+        #[inline]
+        fn consume(&mut self, amt: usize) {
+            self.deref_mut().consume(amt)
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-io-async")))]
+    impl<'a, T> Write for ReferenceMut<'a, T>
+    where
+        T: Write + ?Sized,
+    {
+        #[inline]
+        async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.deref_mut().write(buf).await
+        }
+
+        #[inline]
+        async fn flush(&mut self) -> Result<(), Self::Error> {
+            self.deref_mut().flush().await
+        }
+
+        #[inline]
+        async fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+            self.deref_mut().write_all(buf).await
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-io-async")))]
+    impl<'a, T> Seek for ReferenceMut<'a, T>
+    where
+        T: Seek + ?Sized,
+    {
+        #[inline]
+        async fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error> {
+            self.deref_mut().seek(pos).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "std", feature = "embedded-io"))]
+    use crate::OwnOrBorrow;
+    #[cfg(any(feature = "std", feature = "embedded-io"))]
+    use core::cell::RefCell;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_io_through_guard() {
+        use std::io::{BufRead, Cursor, Write};
+
+        let cell = RefCell::new(Cursor::new(Vec::new()));
+        let mut value = OwnOrBorrow::from(&cell);
+        writeln!(value.borrow_mut(), "hello").expect("failed to write");
+        cell.borrow_mut().set_position(0);
+
+        let mut line = String::new();
+        value
+            .borrow_mut()
+            .read_line(&mut line)
+            .expect("failed to read");
+        assert_eq!(line, "hello\n");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_io_as_trait_object() {
+        fn copy(reader: &mut dyn std::io::Read, writer: &mut dyn std::io::Write) -> u64 {
+            std::io::copy(reader, writer).expect("failed to copy")
+        }
+
+        let mut source = OwnOrBorrow::own(&b"data"[..]);
+        let mut sink = OwnOrBorrow::own(Vec::new());
+        assert_eq!(copy(&mut source.borrow_mut(), &mut sink.borrow_mut()), 4);
+        assert_eq!(sink.borrow().as_slice(), b"data");
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-io")]
+    fn embedded_io_read_through_guard() {
+        use embedded_io::Read;
+
+        let cell = RefCell::new(&b"hello"[..]);
+        let mut value = OwnOrBorrow::from(&cell);
+        let mut buf = [0u8; 5];
+        value
+            .borrow_mut()
+            .read_exact(&mut buf)
+            .expect("failed to read");
+        assert_eq!(&buf, b"hello");
+        assert!(cell.borrow().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-io")]
+    fn embedded_io_write_through_guard() {
+        use embedded_io::Write;
+
+        let mut buf = [0u8; 4];
+        let mut value = OwnOrBorrow::own(&mut buf[..]);
+        value
+            .borrow_mut()
+            .write_all(b"abcd")
+            .expect("failed to write");
+        assert_eq!(&buf, b"abcd");
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-io-async")]
+    fn embedded_io_async_read_through_guard() {
+        use core::future::Future;
+        use core::task::{Context, Poll, Waker};
+        use embedded_io_async::Read;
+
+        let cell = RefCell::new(&b"hi"[..]);
+        let mut value = OwnOrBorrow::from(&cell);
+        let mut buf = [0u8; 2];
+        let mut guard = value.borrow_mut();
+        let mut context = Context::from_waker(Waker::noop());
+        let poll = core::pin::pin!(guard.read_exact(&mut buf)).poll(&mut context);
+        assert!(matches!(poll, Poll::Ready(Ok(()))));
+        assert_eq!(&buf, b"hi");
+    }
+}
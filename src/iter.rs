@@ -0,0 +1,160 @@
+//! Iteration through the wrapper and its guards.
+
+use crate::{OwnOrBorrow, Reference, ReferenceMut};
+use core::ops::{Deref, DerefMut};
+
+impl<'a, T> OwnOrBorrow<'a, T> {
+    /// Calls the closure on each element of the borrowed collection.
+    ///
+    /// The borrow is released when the iteration ends.
+    ///
+    /// ```
+    /// use own_or_borrow::OwnOrBorrow;
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(vec![1, 2, 3]);
+    /// let mut sum = 0;
+    /// OwnOrBorrow::from(&cell).for_each(|item| sum += item);
+    /// assert_eq!(sum, 6);
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn for_each<F>(&self, f: F)
+    where
+        for<'b> &'b T: IntoIterator,
+        F: for<'b> FnMut(<&'b T as IntoIterator>::Item),
+    {
+        self.borrow().deref().into_iter().for_each(f)
+    }
+
+    /// Calls the closure on each element of the mutably borrowed collection.
+    ///
+    /// The borrow is released when the iteration ends.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed, or if it is a plain shared reference
+    /// ([`OwnOrBorrow::Borrowed`]).
+    #[track_caller]
+    pub fn for_each_mut<F>(&mut self, f: F)
+    where
+        for<'b> &'b mut T: IntoIterator,
+        F: for<'b> FnMut(<&'b mut T as IntoIterator>::Item),
+    {
+        self.borrow_mut().deref_mut().into_iter().for_each(f)
+    }
+}
+
+impl<'a, T> IntoIterator for OwnOrBorrow<'a, T>
+where
+    T: IntoIterator + Clone,
+{
+    type Item = T::Item;
+    type IntoIter = T::IntoIter;
+
+    /// Iterates the owned value, cloning it first if it is borrowed, just like
+    /// [`OwnOrBorrow::into_owned`].
+    ///
+    /// ## Panics
+    /// Panics if the value is a [`RefCell`](core::cell::RefCell) reference that is currently
+    /// mutably borrowed.
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_owned().into_iter()
+    }
+}
+
+/// Allows iterating a guard directly, e.g. `for item in &value.borrow() { .. }`.
+impl<'r, 'a, T> IntoIterator for &'r Reference<'a, T>
+where
+    T: ?Sized,
+    &'r T: IntoIterator,
+{
+    type Item = <&'r T as IntoIterator>::Item;
+    type IntoIter = <&'r T as IntoIterator>::IntoIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref().into_iter()
+    }
+}
+
+impl<'r, 'a, T> IntoIterator for &'r ReferenceMut<'a, T>
+where
+    T: ?Sized,
+    &'r T: IntoIterator,
+{
+    type Item = <&'r T as IntoIterator>::Item;
+    type IntoIter = <&'r T as IntoIterator>::IntoIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref().into_iter()
+    }
+}
+
+/// Allows iterating a guard mutably, e.g. `for item in &mut value.borrow_mut() { .. }`.
+impl<'r, 'a, T> IntoIterator for &'r mut ReferenceMut<'a, T>
+where
+    T: ?Sized,
+    &'r mut T: IntoIterator,
+{
+    type Item = <&'r mut T as IntoIterator>::Item;
+    type IntoIter = <&'r mut T as IntoIterator>::IntoIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref_mut().into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    #[test]
+    fn iterate_guard_in_for_loop() {
+        let cell = RefCell::new([1, 2, 3]);
+        let value = OwnOrBorrow::from(&cell);
+        let mut sum = 0;
+        for item in &value.borrow() {
+            sum += item;
+        }
+        assert_eq!(sum, 6);
+        assert!(cell.try_borrow_mut().is_ok());
+    }
+
+    #[test]
+    fn iterate_guard_mutably() {
+        let cell = RefCell::new([1, 2, 3]);
+        let mut value = OwnOrBorrow::from(&cell);
+        for item in &mut value.borrow_mut() {
+            *item *= 2;
+        }
+        assert_eq!(*cell.borrow(), [2, 4, 6]);
+    }
+
+    #[test]
+    fn for_each_mut_modifies_items() {
+        let mut data = [1, 2, 3];
+        let mut value = OwnOrBorrow::from(&mut data);
+        value.for_each_mut(|item| *item += 1);
+        let mut items = [0; 3];
+        let mut index = 0;
+        value.for_each(|item| {
+            items[index] = *item;
+            index += 1;
+        });
+        assert_eq!(items, [2, 3, 4]);
+    }
+
+    #[test]
+    fn into_iter_owned() {
+        let value = OwnOrBorrow::own([1, 2, 3]);
+        assert_eq!(value.into_iter().sum::<i32>(), 6);
+
+        let cell = RefCell::new([4, 5]);
+        assert_eq!(OwnOrBorrow::from(&cell).into_iter().sum::<i32>(), 9);
+    }
+}
@@ -0,0 +1,315 @@
+//! Lazily initialized counterpart to [`OwnOrBorrow`](crate::OwnOrBorrow).
+
+use crate::audit::{self, Source, Wrapper};
+use crate::{BorrowFault, Reference, ReferenceMut};
+use core::cell::{Cell, OnceCell, RefCell};
+
+/// A type that provides either a lazily computed owned value or a [`RefCell`] borrowed
+/// reference to a value.
+///
+/// The [`LazyOwnOrBorrow::Lazy`] variant computes the value from a closure on first access,
+/// which allows passing either a precomputed value or a recipe to compute one through the
+/// same type.
+///
+/// ```
+/// use own_or_borrow::LazyOwnOrBorrow;
+///
+/// fn sum(table: &LazyOwnOrBorrow<'_, Vec<u32>>) -> u32 {
+///     table.borrow().iter().sum()
+/// }
+///
+/// let precomputed = vec![1, 2, 3];
+/// assert_eq!(sum(&LazyOwnOrBorrow::borrowed(&precomputed)), 6);
+/// assert_eq!(sum(&LazyOwnOrBorrow::lazy(|| (1..=3).collect())), 6);
+/// ```
+pub enum LazyOwnOrBorrow<'a, T, F = fn() -> T> {
+    /// An owned value.
+    Owned(T),
+    /// An owned value that is computed on first access by the closure, which is taken out of
+    /// its [`Cell`] when called.
+    Lazy(OnceCell<T>, Cell<Option<F>>),
+    /// A reference to a [`RefCell`].
+    RefCellRef(&'a RefCell<T>),
+    /// A plain shared reference. The value cannot be borrowed mutably.
+    Borrowed(&'a T),
+    /// A plain mutable reference.
+    BorrowedMut(&'a mut T),
+}
+
+impl<'a, T, F> LazyOwnOrBorrow<'a, T, F>
+where
+    F: FnOnce() -> T,
+{
+    /// Initializes a new instance that owns data.
+    pub const fn own(value: T) -> Self {
+        Self::Owned(value)
+    }
+
+    /// Initializes a new instance that computes the owned value on first access.
+    pub const fn lazy(f: F) -> Self {
+        Self::Lazy(OnceCell::new(), Cell::new(Some(f)))
+    }
+
+    /// Initializes a new instance from a plain shared reference.
+    ///
+    /// Since the value is only shared, [`borrow_mut`](Self::borrow_mut) will panic and
+    /// [`try_borrow_mut`](Self::try_borrow_mut) will fail with [`BorrowFault::Shared`].
+    pub const fn borrowed(value: &'a T) -> Self {
+        Self::Borrowed(value)
+    }
+
+    /// Initializes a new instance from a plain mutable reference.
+    pub const fn borrowed_mut(value: &'a mut T) -> Self {
+        Self::BorrowedMut(value)
+    }
+
+    /// Returns `false` if the value is a [`LazyOwnOrBorrow::Lazy`] value that was not
+    /// computed yet.
+    pub fn is_initialized(&self) -> bool {
+        match self {
+            LazyOwnOrBorrow::Lazy(value, _) => value.get().is_some(),
+            _ => true,
+        }
+    }
+
+    /// Borrows the inner value, computing it first if needed.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed, or if the initialization closure
+    /// panicked before.
+    #[track_caller]
+    pub fn borrow(&self) -> Reference<'_, T> {
+        match self {
+            LazyOwnOrBorrow::Owned(value) => {
+                audit::unchecked(Wrapper::LazyOwnOrBorrow, Source::Owned, false);
+                Reference::Borrowed(value)
+            }
+            LazyOwnOrBorrow::Lazy(value, init) => {
+                audit::unchecked(Wrapper::LazyOwnOrBorrow, Source::Lazy, false);
+                Reference::Borrowed(force(value, init))
+            }
+            LazyOwnOrBorrow::RefCellRef(ref_cell) => Reference::RefCellRef(
+                audit::borrow(Wrapper::LazyOwnOrBorrow, ref_cell, Source::RefCellRef),
+                ref_cell,
+            ),
+            LazyOwnOrBorrow::Borrowed(value) => {
+                audit::unchecked(Wrapper::LazyOwnOrBorrow, Source::Borrowed, false);
+                Reference::Borrowed(value)
+            }
+            LazyOwnOrBorrow::BorrowedMut(value) => {
+                audit::unchecked(Wrapper::LazyOwnOrBorrow, Source::BorrowedMut, false);
+                Reference::Borrowed(value)
+            }
+        }
+    }
+
+    /// Borrows the inner value mutably, computing it first if needed.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed, if it is a plain shared reference
+    /// ([`LazyOwnOrBorrow::Borrowed`]), or if the initialization closure panicked before.
+    #[track_caller]
+    pub fn borrow_mut(&mut self) -> ReferenceMut<'_, T> {
+        match self {
+            LazyOwnOrBorrow::Owned(value) => {
+                audit::unchecked(Wrapper::LazyOwnOrBorrow, Source::Owned, true);
+                ReferenceMut::Borrowed(value)
+            }
+            LazyOwnOrBorrow::Lazy(value, init) => {
+                audit::unchecked(Wrapper::LazyOwnOrBorrow, Source::Lazy, true);
+                force(value, init);
+                match value.get_mut() {
+                    Some(value) => ReferenceMut::Borrowed(value),
+                    None => unreachable!("the value was just computed"),
+                }
+            }
+            LazyOwnOrBorrow::RefCellRef(ref_cell) => ReferenceMut::RefCellRef(
+                audit::borrow_mut(Wrapper::LazyOwnOrBorrow, ref_cell, Source::RefCellRef),
+                ref_cell,
+            ),
+            LazyOwnOrBorrow::Borrowed(_) => panic!("{}", BorrowFault::Shared),
+            LazyOwnOrBorrow::BorrowedMut(value) => {
+                audit::unchecked(Wrapper::LazyOwnOrBorrow, Source::BorrowedMut, true);
+                ReferenceMut::Borrowed(value)
+            }
+        }
+    }
+
+    /// Attempts to borrow the inner value, computing it first if needed.
+    ///
+    /// Unlike [`borrow`](Self::borrow), this returns an error instead of panicking if the
+    /// value is currently mutably borrowed.
+    ///
+    /// ## Panics
+    /// Panics if the initialization closure panicked before.
+    pub fn try_borrow(&self) -> Result<Reference<'_, T>, BorrowFault> {
+        match self {
+            LazyOwnOrBorrow::RefCellRef(ref_cell) => Ok(Reference::RefCellRef(
+                audit::try_borrow(Wrapper::LazyOwnOrBorrow, ref_cell, Source::RefCellRef)?,
+                ref_cell,
+            )),
+            other => Ok(other.borrow()),
+        }
+    }
+
+    /// Attempts to borrow the inner value mutably, computing it first if needed.
+    ///
+    /// Unlike [`borrow_mut`](Self::borrow_mut), this returns an error instead of panicking if
+    /// the value is currently borrowed or is a plain shared reference.
+    ///
+    /// ## Panics
+    /// Panics if the initialization closure panicked before.
+    pub fn try_borrow_mut(&mut self) -> Result<ReferenceMut<'_, T>, BorrowFault> {
+        match self {
+            LazyOwnOrBorrow::RefCellRef(ref_cell) => Ok(ReferenceMut::RefCellRef(
+                audit::try_borrow_mut(Wrapper::LazyOwnOrBorrow, ref_cell, Source::RefCellRef)?,
+                ref_cell,
+            )),
+            LazyOwnOrBorrow::Borrowed(_) => Err(BorrowFault::Shared),
+            other => Ok(other.borrow_mut()),
+        }
+    }
+}
+
+impl<'a, T, F> From<&'a RefCell<T>> for LazyOwnOrBorrow<'a, T, F> {
+    #[inline]
+    fn from(value: &'a RefCell<T>) -> Self {
+        Self::RefCellRef(value)
+    }
+}
+
+impl<'a, T, F> From<&'a mut T> for LazyOwnOrBorrow<'a, T, F> {
+    #[inline]
+    fn from(value: &'a mut T) -> Self {
+        Self::BorrowedMut(value)
+    }
+}
+
+impl<'a, T, F> core::fmt::Debug for LazyOwnOrBorrow<'a, T, F>
+where
+    T: core::fmt::Debug,
+    F: FnOnce() -> T,
+{
+    /// Formats the inner value. Unlike [`borrow`](LazyOwnOrBorrow::borrow), this does not
+    /// compute a lazy value and prints `<uninit>` instead. A value that is currently mutably
+    /// borrowed is printed as `<mutably borrowed>`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LazyOwnOrBorrow::Lazy(value, _) => match value.get() {
+                Some(value) => core::fmt::Debug::fmt(value, f),
+                None => f.write_str("<uninit>"),
+            },
+            other => match other.try_borrow() {
+                Ok(data) => core::fmt::Debug::fmt(data.as_ref(), f),
+                Err(_) => f.write_str("<mutably borrowed>"),
+            },
+        }
+    }
+}
+
+impl<'a, T, F> core::fmt::Display for LazyOwnOrBorrow<'a, T, F>
+where
+    T: core::fmt::Display,
+    F: FnOnce() -> T,
+{
+    /// Formats the inner value, or prints `<mutably borrowed>` if it is currently mutably
+    /// borrowed. Lazy values are computed first.
+    ///
+    /// ## Panics
+    /// Panics if the initialization closure panics.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.try_borrow() {
+            Ok(data) => core::fmt::Display::fmt(data.as_ref(), f),
+            Err(_) => f.write_str("<mutably borrowed>"),
+        }
+    }
+}
+
+/// Computes the value of a [`LazyOwnOrBorrow::Lazy`] instance if needed.
+#[track_caller]
+fn force<'l, T, F>(value: &'l OnceCell<T>, init: &Cell<Option<F>>) -> &'l T
+where
+    F: FnOnce() -> T,
+{
+    value.get_or_init(|| match init.take() {
+        Some(f) => f(),
+        None => panic!("LazyOwnOrBorrow instance has previously been poisoned"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    #[test]
+    fn lazy_computes_once() {
+        let calls = Cell::new(0);
+        let value = LazyOwnOrBorrow::lazy(|| {
+            calls.set(calls.get() + 1);
+            42
+        });
+        assert!(!value.is_initialized());
+        assert_eq!(value.borrow().as_ref(), &42);
+        assert_eq!(value.borrow().as_ref(), &42);
+        assert!(value.is_initialized());
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn lazy_borrow_mut() {
+        let mut value = LazyOwnOrBorrow::lazy(|| 42);
+        *value.borrow_mut() += 1;
+        assert_eq!(value.borrow().as_ref(), &43);
+    }
+
+    #[test]
+    fn borrow_refcell_ref() {
+        let cell = RefCell::new(42);
+        let mut value = LazyOwnOrBorrow::<'_, i32>::from(&cell);
+        *value.borrow_mut() += 1;
+        assert_eq!(*cell.borrow(), 43);
+
+        let _guard = cell.borrow();
+        assert!(matches!(
+            value.try_borrow_mut(),
+            Err(BorrowFault::BorrowMut(_))
+        ));
+    }
+
+    #[test]
+    fn try_borrow_mut_fails_for_shared_reference() {
+        let mut value = LazyOwnOrBorrow::<'_, i32>::borrowed(&42);
+        assert!(matches!(value.try_borrow_mut(), Err(BorrowFault::Shared)));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn panicking_closure_poisons_value() {
+        let value = LazyOwnOrBorrow::<'_, i32, _>::lazy(|| panic!("failed to compute"));
+        let first = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| *value.borrow()));
+        assert!(first.is_err());
+        let second = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| *value.borrow()));
+        assert!(second.is_err());
+        assert!(!value.is_initialized());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn debug_does_not_force() {
+        let value = LazyOwnOrBorrow::<'_, i32>::lazy(|| 42);
+        assert_eq!(format!("{:?}", value), "<uninit>");
+        assert_eq!(format!("{}", value), "42");
+        assert_eq!(format!("{:?}", value), "42");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn format_while_mutably_borrowed() {
+        let cell = RefCell::new(42);
+        let value = LazyOwnOrBorrow::<_, fn() -> i32>::from(&cell);
+        let _guard = cell.borrow_mut();
+        assert_eq!(format!("{:?}", value), "<mutably borrowed>");
+        assert_eq!(format!("{}", value), "<mutably borrowed>");
+    }
+}
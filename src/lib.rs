@@ -1,15 +1,51 @@
 //! # Own or borrow your data.
 //!
-//! This crate provides the [`OwnOrBorrow`] type that wraps either owned data or a [`RefCell`]
-//! borrowed reference to it. Think `Cow` for borrowing.
+//! This crate provides the [`OwnOrBorrow`] type that wraps either owned data, a plain reference
+//! or a [`RefCell`] borrowed reference to it. Think `Cow` for borrowing.
+//!
+//! For unsized values such as trait objects or slices, use [`OwnOrBorrowUnsized`] instead.
+//! [`LazyOwnOrBorrow`] computes its owned value on first access. Generic code can accept
+//! any of these types, as well as custom storage, through the [`BorrowBackend`] trait.
+//! For small [`Copy`] values, [`OwnOrCell`] avoids run-time borrow checks altogether.
+//! [`borrow_all`] borrows several values at once.
 //!
 //! ## `no_std` vs. `std`
 //!
 //! To use the crate in a `no_std` context, disable the `std` feature.
 //!
 //! ## Crate features
-//! * `std` - Enables `std`; disabling enters `no_std` mode.
-//! * `defmt` - Enables deferred formatting support via the [defmt](https://crates.io/crates/defmt) crate.
+//! * `std` - Enables `std`; disabling enters `no_std` mode. Also enables the thread-safe
+//!   `OwnOrLock` type backed by `RwLock` or `Mutex`, and forwards the `std::io` traits
+//!   through [`ReferenceMut`]. Implies `alloc`.
+//! * `alloc` - Enables the reference-counted `OwnOrBorrow::RefCellRc` variant, the
+//!   boxed `OwnOrBorrowUnsized::Boxed` variant, the `OwnOrWeak` type holding weak
+//!   references and the `OwnOrBorrowStr` and `OwnOrBorrowBytes` aliases.
+//! * `arbitrary` - Implements `Arbitrary` via the [arbitrary](https://crates.io/crates/arbitrary)
+//!   crate, generating both the [`OwnOrBorrow::Owned`] and [`OwnOrBorrow::RefCell`] variants.
+//! * `atomic_refcell` - Enables the [`Sync`] `OwnOrAtomicBorrow` type backed by the
+//!   [atomic_refcell](https://crates.io/crates/atomic_refcell) crate.
+//! * `borrow-diagnostics` - Records where borrows through a [`ScopedBorrow`] are taken and
+//!   names the last one in conflict messages.
+//! * `critical-section` - Enables the interrupt-safe `OwnOrCriticalSection` type built on the
+//!   [critical-section](https://crates.io/crates/critical-section) crate.
+//! * `defmt` - Enables deferred formatting support via the [defmt](https://crates.io/crates/defmt) crate
+//!   for the wrappers, guards and errors.
+//! * `defmt-audit` - Emits `defmt::trace!` events naming the wrapper when borrows are
+//!   acquired or conflict. Releases are not traced. Implies `defmt`.
+//! * `embedded-io` - Forwards the [embedded-io](https://crates.io/crates/embedded-io) traits
+//!   through [`ReferenceMut`].
+//! * `embedded-io-async` - Forwards the [embedded-io-async](https://crates.io/crates/embedded-io-async)
+//!   traits through [`ReferenceMut`]. Implies `embedded-io`.
+//! * `parking_lot` - Adds `OwnOrLock` variants backed by the non-poisoning
+//!   [parking_lot](https://crates.io/crates/parking_lot) locks. Implies `std`.
+//! * `serde` - Implements `Serialize` and `Deserialize` via the [serde](https://crates.io/crates/serde)
+//!   crate. Values are serialized transparently and deserialized into [`OwnOrBorrow::Owned`].
+//! * `spin` - Enables the [`Sync`] `OwnOrSpin` type backed by the spinning locks of the
+//!   [spin](https://crates.io/crates/spin) crate, for sharing state across cores in `no_std`.
+//! * `tokio` - Enables the asynchronous `OwnOrBorrowAsync` type backed by the `tokio::sync`
+//!   locks. Implies `std`.
+//! * `zeroize` - Implements `Zeroize` via the [zeroize](https://crates.io/crates/zeroize) crate,
+//!   wiping owned values. Borrowed values are left untouched.
 //!
 //! ## Examples
 //!
@@ -24,6 +60,18 @@
 //! assert_eq!(value.borrow_mut().as_mut(), &mut 42);
 //! ```
 //!
+//! Plain references work as well:
+//!
+//! ```
+//! use own_or_borrow::OwnOrBorrow;
+//!
+//! let mut data = 42;
+//! let mut value = OwnOrBorrow::from(&mut data);
+//! *value.borrow_mut() += 1;
+//!
+//! assert_eq!(data, 43);
+//! ```
+//!
 //! You can create an [`OwnOrBorrow`] from a [`RefCell`] and treat it the same way:
 //!
 //! ```
@@ -42,13 +90,99 @@
 // Enables the `doc_cfg` feature when the `docsrs` configuration attribute is defined.
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod arith;
+#[cfg(feature = "tokio")]
+mod async_lock;
+#[cfg(feature = "atomic_refcell")]
+mod atomic;
+mod audit;
+mod backend;
+mod batch;
+mod cell;
+mod cmp;
+#[cfg(feature = "critical-section")]
+mod critical_section;
 mod error;
+mod exclusive;
+#[cfg(feature = "defmt")]
+mod format;
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+mod held;
+mod io;
+mod iter;
+mod lazy;
+#[cfg(feature = "std")]
+mod lock;
+mod pin;
+mod scope;
+#[cfg(feature = "zeroize")]
+mod secret;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "spin")]
+mod spin_lock;
+#[cfg(feature = "alloc")]
+mod text;
+mod unsized_borrow;
+#[cfg(feature = "alloc")]
+mod weak;
 
-pub use crate::error::TryIntoError;
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub use crate::async_lock::{AsyncReference, AsyncReferenceMut, OwnOrBorrowAsync};
+#[cfg(feature = "atomic_refcell")]
+#[cfg_attr(docsrs, doc(cfg(feature = "atomic_refcell")))]
+pub use crate::atomic::{AtomicReference, AtomicReferenceMut, OwnOrAtomicBorrow};
+use crate::audit::{Source, Wrapper};
+pub use crate::backend::BorrowBackend;
+pub use crate::batch::{borrow_all, try_borrow_all, BorrowAll, BorrowAllItem};
+pub use crate::cell::{CellSnapshot, OwnOrCell};
+#[cfg(feature = "critical-section")]
+#[cfg_attr(docsrs, doc(cfg(feature = "critical-section")))]
+pub use crate::critical_section::OwnOrCriticalSection;
+#[cfg(feature = "atomic_refcell")]
+#[cfg_attr(docsrs, doc(cfg(feature = "atomic_refcell")))]
+pub use crate::error::AtomicBorrowFault;
+#[cfg(any(feature = "std", feature = "spin"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "spin"))))]
+pub use crate::error::LockError;
+pub use crate::error::{BorrowFault, TryIntoError};
+pub use crate::exclusive::OwnOrBorrowMut;
+pub use crate::held::HeldReference;
+pub use crate::lazy::LazyOwnOrBorrow;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use crate::lock::{LockReference, LockReferenceMut, OwnOrLock};
+pub use crate::pin::{PinReference, PinReferenceMut};
+pub use crate::scope::{ScopedBorrow, ScopedReference, ScopedReferenceMut};
+#[cfg(feature = "spin")]
+#[cfg_attr(docsrs, doc(cfg(feature = "spin")))]
+pub use crate::spin_lock::{OwnOrSpin, SpinReference, SpinReferenceMut};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use crate::text::{OwnOrBorrowBytes, OwnOrBorrowStr, StrLines, StrSplit, StrSplitWhitespace};
+pub use crate::unsized_borrow::{
+    OwnOrBorrowUnsized, OwnOrBorrowUnsizedBytes, OwnOrBorrowUnsizedStr, SliceChunks,
+    SliceChunksMut, SliceWindows,
+};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use crate::weak::OwnOrWeak;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
 use core::cell::{Ref, RefCell, RefMut};
-use core::ops::{Deref, DerefMut};
+use core::ops::{Deref, DerefMut, Index, IndexMut};
 
 /// A type that provides either an owned value or [`RefCell`] borrowed reference to a value.
+///
+/// Comparison and hashing borrow the inner value and panic if it is currently mutably
+/// borrowed. Formatting prints `<mutably borrowed>` instead.
 pub enum OwnOrBorrow<'a, T> {
     /// An owned value.
     Owned(T),
@@ -56,58 +190,647 @@ pub enum OwnOrBorrow<'a, T> {
     RefCell(RefCell<T>),
     /// A borrowed value.
     RefCellRef(&'a RefCell<T>),
+    /// A plain shared reference. The value cannot be borrowed mutably.
+    Borrowed(&'a T),
+    /// A plain mutable reference.
+    BorrowedMut(&'a mut T),
+    /// A shared, reference-counted [`RefCell`]. The wrapper keeps the value alive.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    RefCellRc(Rc<RefCell<T>>),
+}
+
+/// An [`OwnOrBorrow`] that does not borrow from a shorter-lived [`RefCell`].
+///
+/// Use this alias for APIs that return fully owned results, e.g.
+///
+/// ```
+/// use own_or_borrow::Owned;
+///
+/// fn answer() -> Owned<u32> {
+///     Owned::own(42)
+/// }
+///
+/// assert_eq!(answer().borrow().as_ref(), &42);
+/// ```
+pub type Owned<T> = OwnOrBorrow<'static, T>;
+
+/// The borrow state of an [`OwnOrBorrow`], as reported by [`OwnOrBorrow::borrow_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BorrowState {
+    /// The value is not tracked by a [`RefCell`], so borrows are checked at compile time.
+    Untracked,
+    /// The [`RefCell`] is not currently borrowed.
+    Unused,
+    /// The [`RefCell`] is currently borrowed immutably.
+    Reading,
+    /// The [`RefCell`] is currently borrowed mutably.
+    Writing,
 }
 
 /// A reference to borrowed or owned data.
-pub enum Reference<'a, T> {
-    /// An owned value.
+///
+/// The lifetime `'a` is the lifetime of the borrow, not of the [`OwnOrBorrow`] it was
+/// obtained from.
+pub enum Reference<'a, T: ?Sized> {
+    /// A plain reference to the value.
     Borrowed(&'a T),
-    /// A borrowed value.
+    /// A [`RefCell`] borrow guard.
     RefCell(Ref<'a, T>),
-    /// A borrowed value.
-    RefCellRef(&'a Ref<'a, T>),
+    /// A [`RefCell`] borrow guard along with its [`RefCell`], which allows
+    /// [upgrading](Reference::try_upgrade) the borrow. Projections such as
+    /// [`Reference::map`] turn this into [`Reference::RefCell`].
+    RefCellRef(Ref<'a, T>, &'a RefCell<T>),
 }
 
 /// A mutable reference to borrowed or owned data.
-pub enum ReferenceMut<'a, T> {
-    /// An owned value.
+///
+/// The lifetime `'a` is the lifetime of the borrow, not of the [`OwnOrBorrow`] it was
+/// obtained from.
+pub enum ReferenceMut<'a, T: ?Sized> {
+    /// A plain mutable reference to the value.
     Borrowed(&'a mut T),
-    /// A borrowed value.
+    /// A [`RefCell`] mutable borrow guard.
     RefCell(RefMut<'a, T>),
-    /// A borrowed value.
-    RefCellRef(&'a mut RefMut<'a, T>),
+    /// A [`RefCell`] mutable borrow guard along with its [`RefCell`], which allows
+    /// [downgrading](ReferenceMut::downgrade) the borrow. Projections such as
+    /// [`ReferenceMut::map`] turn this into [`ReferenceMut::RefCell`].
+    RefCellRef(RefMut<'a, T>, &'a RefCell<T>),
 }
 
 impl<'a, T> OwnOrBorrow<'a, T> {
     /// Initializes a new instance that owns data.
-    pub fn own(value: T) -> Self {
+    ///
+    /// There is deliberately no `From<T>` implementation, as type inference could then no
+    /// longer tell whether e.g. `OwnOrBorrow::from(RefCell::new(42))` wraps the cell or owns it.
+    pub const fn own(value: T) -> Self {
         Self::Owned(value)
     }
 
+    /// Initializes a new instance from a plain shared reference.
+    ///
+    /// Since the value is only shared, [`borrow_mut`](Self::borrow_mut) will panic and
+    /// [`try_borrow_mut`](Self::try_borrow_mut) will fail with [`BorrowFault::Shared`].
+    ///
+    /// There is deliberately no `From<&T>` implementation, as it would make conversions
+    /// from `&RefCell<T>` ambiguous.
+    pub const fn borrowed(value: &'a T) -> Self {
+        Self::Borrowed(value)
+    }
+
+    /// Initializes a new instance from a plain mutable reference.
+    pub const fn borrowed_mut(value: &'a mut T) -> Self {
+        Self::BorrowedMut(value)
+    }
+
+    /// Initializes a new instance that owns data wrapped in a [`RefCell`].
+    ///
+    /// Unlike `OwnOrBorrow::from(RefCell::new(value))`, this can be evaluated at compile time,
+    /// e.g. to initialize statics behind a suitable cell:
+    ///
+    /// ```
+    /// use own_or_borrow::OwnOrBorrow;
+    ///
+    /// thread_local! {
+    ///     static GAIN: OwnOrBorrow<'static, u32> = const { OwnOrBorrow::new_refcell(3) };
+    /// }
+    ///
+    /// GAIN.with(|gain| *gain.as_refcell().unwrap().borrow_mut() += 1);
+    /// GAIN.with(|gain| assert_eq!(*gain.borrow(), 4));
+    /// ```
+    pub const fn new_refcell(value: T) -> Self {
+        Self::RefCell(RefCell::new(value))
+    }
+
+    /// Initializes a new instance from a reference to a [`RefCell`].
+    ///
+    /// This is the `const` counterpart to `OwnOrBorrow::from(&cell)`.
+    pub const fn from_refcell_ref(value: &'a RefCell<T>) -> Self {
+        Self::RefCellRef(value)
+    }
+
     /// Borrows the inner value.
-    pub fn borrow(&'a self) -> Reference<'a, T> {
+    #[track_caller]
+    pub fn borrow(&self) -> Reference<'_, T> {
         match self {
-            OwnOrBorrow::Owned(value) => Reference::Borrowed(value),
-            OwnOrBorrow::RefCell(ref_cell) => ref_cell.borrow().into(),
-            OwnOrBorrow::RefCellRef(ref_cell) => ref_cell.borrow().into(),
+            OwnOrBorrow::Owned(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrow, Source::Owned, false);
+                Reference::Borrowed(value)
+            }
+            OwnOrBorrow::RefCell(ref_cell) => Reference::RefCellRef(
+                audit::borrow(Wrapper::OwnOrBorrow, ref_cell, Source::RefCell),
+                ref_cell,
+            ),
+            OwnOrBorrow::RefCellRef(ref_cell) => Reference::RefCellRef(
+                audit::borrow(Wrapper::OwnOrBorrow, ref_cell, Source::RefCellRef),
+                ref_cell,
+            ),
+            OwnOrBorrow::Borrowed(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrow, Source::Borrowed, false);
+                Reference::Borrowed(value)
+            }
+            OwnOrBorrow::BorrowedMut(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrow, Source::BorrowedMut, false);
+                Reference::Borrowed(value)
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(ref_cell) => Reference::RefCellRef(
+                audit::borrow(Wrapper::OwnOrBorrow, ref_cell, Source::RefCellRc),
+                ref_cell,
+            ),
         }
     }
 
     /// Borrows the inner value mutably.
-    pub fn borrow_mut(&'a mut self) -> ReferenceMut<'a, T> {
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed, or if it is a plain shared reference
+    /// ([`OwnOrBorrow::Borrowed`]).
+    #[track_caller]
+    pub fn borrow_mut(&mut self) -> ReferenceMut<'_, T> {
+        match self {
+            OwnOrBorrow::Owned(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrow, Source::Owned, true);
+                ReferenceMut::Borrowed(value)
+            }
+            OwnOrBorrow::RefCell(ref_cell) => ReferenceMut::RefCellRef(
+                audit::borrow_mut(Wrapper::OwnOrBorrow, ref_cell, Source::RefCell),
+                ref_cell,
+            ),
+            OwnOrBorrow::RefCellRef(ref_cell) => ReferenceMut::RefCellRef(
+                audit::borrow_mut(Wrapper::OwnOrBorrow, ref_cell, Source::RefCellRef),
+                ref_cell,
+            ),
+            OwnOrBorrow::Borrowed(_) => panic!("{}", BorrowFault::Shared),
+            OwnOrBorrow::BorrowedMut(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrow, Source::BorrowedMut, true);
+                ReferenceMut::Borrowed(value)
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(ref_cell) => ReferenceMut::RefCellRef(
+                audit::borrow_mut(Wrapper::OwnOrBorrow, ref_cell, Source::RefCellRc),
+                ref_cell,
+            ),
+        }
+    }
+
+    /// Attempts to borrow the inner value.
+    ///
+    /// Unlike [`borrow`](Self::borrow), this returns an error instead of panicking if the
+    /// value is currently mutably borrowed.
+    pub fn try_borrow(&self) -> Result<Reference<'_, T>, BorrowFault> {
+        match self {
+            OwnOrBorrow::Owned(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrow, Source::Owned, false);
+                Ok(Reference::Borrowed(value))
+            }
+            OwnOrBorrow::RefCell(ref_cell) => Ok(Reference::RefCellRef(
+                audit::try_borrow(Wrapper::OwnOrBorrow, ref_cell, Source::RefCell)?,
+                ref_cell,
+            )),
+            OwnOrBorrow::RefCellRef(ref_cell) => Ok(Reference::RefCellRef(
+                audit::try_borrow(Wrapper::OwnOrBorrow, ref_cell, Source::RefCellRef)?,
+                ref_cell,
+            )),
+            OwnOrBorrow::Borrowed(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrow, Source::Borrowed, false);
+                Ok(Reference::Borrowed(value))
+            }
+            OwnOrBorrow::BorrowedMut(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrow, Source::BorrowedMut, false);
+                Ok(Reference::Borrowed(value))
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(ref_cell) => Ok(Reference::RefCellRef(
+                audit::try_borrow(Wrapper::OwnOrBorrow, ref_cell, Source::RefCellRc)?,
+                ref_cell,
+            )),
+        }
+    }
+
+    /// Attempts to borrow the inner value mutably.
+    ///
+    /// Unlike [`borrow_mut`](Self::borrow_mut), this returns an error instead of panicking if
+    /// the value is currently borrowed or is a plain shared reference.
+    pub fn try_borrow_mut(&mut self) -> Result<ReferenceMut<'_, T>, BorrowFault> {
+        match self {
+            OwnOrBorrow::Owned(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrow, Source::Owned, true);
+                Ok(ReferenceMut::Borrowed(value))
+            }
+            OwnOrBorrow::RefCell(ref_cell) => Ok(ReferenceMut::RefCellRef(
+                audit::try_borrow_mut(Wrapper::OwnOrBorrow, ref_cell, Source::RefCell)?,
+                ref_cell,
+            )),
+            OwnOrBorrow::RefCellRef(ref_cell) => Ok(ReferenceMut::RefCellRef(
+                audit::try_borrow_mut(Wrapper::OwnOrBorrow, ref_cell, Source::RefCellRef)?,
+                ref_cell,
+            )),
+            OwnOrBorrow::Borrowed(_) => Err(BorrowFault::Shared),
+            OwnOrBorrow::BorrowedMut(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrow, Source::BorrowedMut, true);
+                Ok(ReferenceMut::Borrowed(value))
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(ref_cell) => Ok(ReferenceMut::RefCellRef(
+                audit::try_borrow_mut(Wrapper::OwnOrBorrow, ref_cell, Source::RefCellRc)?,
+                ref_cell,
+            )),
+        }
+    }
+
+    /// Returns `true` if the wrapper owns the value, i.e. for [`OwnOrBorrow::Owned`] and
+    /// [`OwnOrBorrow::RefCell`].
+    pub fn is_owned(&self) -> bool {
+        matches!(self, OwnOrBorrow::Owned(_) | OwnOrBorrow::RefCell(_))
+    }
+
+    /// Returns `true` if the wrapper borrows the value for the lifetime `'a`, i.e. for
+    /// [`OwnOrBorrow::RefCellRef`], [`OwnOrBorrow::Borrowed`] and [`OwnOrBorrow::BorrowedMut`].
+    ///
+    /// Reference-counted values are neither owned nor borrowed.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(
+            self,
+            OwnOrBorrow::RefCellRef(_) | OwnOrBorrow::Borrowed(_) | OwnOrBorrow::BorrowedMut(_)
+        )
+    }
+
+    /// Returns a reference to the value if it is an [`OwnOrBorrow::Owned`] value.
+    pub fn as_owned(&self) -> Option<&T> {
+        match self {
+            OwnOrBorrow::Owned(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the [`RefCell`] if the value is stored in one.
+    pub fn as_refcell(&self) -> Option<&RefCell<T>> {
+        match self {
+            OwnOrBorrow::RefCell(ref_cell) => Some(ref_cell),
+            OwnOrBorrow::RefCellRef(ref_cell) => Some(ref_cell),
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(ref_cell) => Some(ref_cell),
+            _ => None,
+        }
+    }
+
+    /// Inspects whether the value is currently borrowed.
+    ///
+    /// This does not emit audit events.
+    pub fn borrow_state(&self) -> BorrowState {
+        let Some(ref_cell) = self.as_refcell() else {
+            return BorrowState::Untracked;
+        };
+        if ref_cell.try_borrow_mut().is_ok() {
+            BorrowState::Unused
+        } else if ref_cell.try_borrow().is_ok() {
+            BorrowState::Reading
+        } else {
+            BorrowState::Writing
+        }
+    }
+
+    /// Returns a mutable reference to the inner value without run-time borrow checks.
+    ///
+    /// Since `&mut self` guarantees exclusive access, this uses [`RefCell::get_mut`] for owned
+    /// cells and succeeds for `OwnOrBorrow::RefCellRc` values without other strong
+    /// references. Returns `None` for [`RefCell`] references, plain shared references and
+    /// shared `Rc` instances.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
         match self {
-            OwnOrBorrow::Owned(value) => ReferenceMut::Borrowed(value),
-            OwnOrBorrow::RefCell(ref_cell) => ref_cell.borrow_mut().into(),
-            OwnOrBorrow::RefCellRef(ref_cell) => ref_cell.borrow_mut().into(),
+            OwnOrBorrow::Owned(value) => Some(value),
+            OwnOrBorrow::RefCell(ref_cell) => Some(ref_cell.get_mut()),
+            OwnOrBorrow::RefCellRef(_) => None,
+            OwnOrBorrow::Borrowed(_) => None,
+            OwnOrBorrow::BorrowedMut(value) => Some(value),
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(ref_cell) => Rc::get_mut(ref_cell).map(RefCell::get_mut),
         }
     }
 
+    /// Runs the closure with shared access to the inner value.
+    ///
+    /// The borrow is released when the closure returns.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        f(&self.borrow())
+    }
+
+    /// Runs the closure with mutable access to the inner value.
+    ///
+    /// The borrow is released when the closure returns.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed, or if it is a plain shared reference
+    /// ([`OwnOrBorrow::Borrowed`]).
+    #[track_caller]
+    pub fn with_mut<R, F>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        f(&mut self.borrow_mut())
+    }
+
+    /// Replaces the inner value, returning the old value.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed, or if it is a plain shared reference
+    /// ([`OwnOrBorrow::Borrowed`]).
+    #[track_caller]
+    pub fn replace(&mut self, value: T) -> T {
+        core::mem::replace(&mut self.borrow_mut(), value)
+    }
+
+    /// Updates the inner value using the closure and returns the new value, like
+    /// [`Cell::update`](core::cell::Cell::update).
+    ///
+    /// ```
+    /// use own_or_borrow::OwnOrBorrow;
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(1.5);
+    /// let mut accumulator = OwnOrBorrow::from(&cell);
+    /// assert_eq!(accumulator.update(|value| value * 2.0), 3.0);
+    /// assert_eq!(*cell.borrow(), 3.0);
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed, or if it is a plain shared reference
+    /// ([`OwnOrBorrow::Borrowed`]).
+    #[track_caller]
+    pub fn update<F>(&mut self, f: F) -> T
+    where
+        T: Copy,
+        F: FnOnce(T) -> T,
+    {
+        let mut value = self.borrow_mut();
+        *value = f(*value);
+        *value
+    }
+
+    /// Swaps the inner value with the inner value of `other`.
+    ///
+    /// ## Panics
+    /// Panics if either value is currently borrowed, if either is a plain shared reference
+    /// ([`OwnOrBorrow::Borrowed`]), or if both refer to the same [`RefCell`].
+    #[track_caller]
+    pub fn swap(&mut self, other: &mut OwnOrBorrow<'_, T>) {
+        core::mem::swap(&mut *self.borrow_mut(), &mut *other.borrow_mut())
+    }
+
+    /// Takes the inner value, leaving [`Default::default()`] in its place.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed, or if it is a plain shared reference
+    /// ([`OwnOrBorrow::Borrowed`]).
+    #[track_caller]
+    pub fn take(&mut self) -> T
+    where
+        T: Default,
+    {
+        self.replace(T::default())
+    }
+
     /// Implements [`TryInto`] behavior for owned variants.
-    pub fn try_into_owned(self) -> Result<T, TryIntoError> {
+    ///
+    /// A blanket `impl<T> TryFrom<OwnOrBorrow<'_, T>> for T` is rejected by the coherence
+    /// rules (`T` would be an uncovered type parameter, see `E0210`), so this method is the
+    /// conversion to use in generic code.
+    ///
+    /// On failure, the wrapper is returned in the error and can be recovered with
+    /// [`TryIntoError::into_inner`].
+    pub fn try_into_owned(self) -> Result<T, TryIntoError<Self>> {
         match self {
             OwnOrBorrow::Owned(value) => Ok(value),
-            OwnOrBorrow::RefCell(_) => Err(TryIntoError::NotConvertible),
-            OwnOrBorrow::RefCellRef(_) => Err(TryIntoError::NotConvertible),
+            other => Err(TryIntoError::NotConvertible(other)),
+        }
+    }
+
+    /// Extracts the value if this instance is its only owner.
+    ///
+    /// This succeeds for owned values, owned [`RefCell`] instances and
+    /// `OwnOrBorrow::RefCellRc` values without other strong references, similar to
+    /// `Rc::try_unwrap`. Otherwise, the wrapper is returned unchanged.
+    pub fn try_unwrap(self) -> Result<T, Self> {
+        match self {
+            OwnOrBorrow::Owned(value) => Ok(value),
+            OwnOrBorrow::RefCell(ref_cell) => Ok(ref_cell.into_inner()),
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(ref_cell) => Rc::try_unwrap(ref_cell)
+                .map(RefCell::into_inner)
+                .map_err(OwnOrBorrow::RefCellRc),
+            other => Err(other),
+        }
+    }
+
+    /// Converts the wrapper into a [`RefCell`] if this instance is the only owner of the
+    /// value.
+    ///
+    /// Unlike the [`TryInto`] conversion, which only accepts [`OwnOrBorrow::RefCell`], this
+    /// also wraps owned values and unwraps `OwnOrBorrow::RefCellRc` values without other
+    /// strong references. Otherwise, the wrapper is returned unchanged.
+    ///
+    /// ```
+    /// use own_or_borrow::OwnOrBorrow;
+    ///
+    /// let cell = OwnOrBorrow::own(42).into_refcell().expect("value is owned");
+    /// assert_eq!(*cell.borrow(), 42);
+    /// ```
+    pub fn into_refcell(self) -> Result<RefCell<T>, Self> {
+        match self {
+            OwnOrBorrow::RefCell(ref_cell) => Ok(ref_cell),
+            other => other.try_unwrap().map(RefCell::new),
+        }
+    }
+
+    /// Converts the wrapper into an [`Owned`] wrapper if it does not borrow from anything.
+    ///
+    /// This succeeds for owned values, owned [`RefCell`] instances and
+    /// `OwnOrBorrow::RefCellRc` values, which keep their variant. References cannot outlive
+    /// `'a`, so the wrapper is returned unchanged.
+    ///
+    /// ```
+    /// use own_or_borrow::{OwnOrBorrow, Owned};
+    ///
+    /// struct Config {
+    ///     name: Owned<String>,
+    /// }
+    ///
+    /// let name = OwnOrBorrow::own(String::from("sensor"));
+    /// let config = Config {
+    ///     name: name.into_static().expect("value is owned"),
+    /// };
+    /// assert_eq!(*config.name.borrow(), "sensor");
+    /// ```
+    pub fn into_static(self) -> Result<Owned<T>, Self> {
+        match self {
+            OwnOrBorrow::Owned(value) => Ok(OwnOrBorrow::Owned(value)),
+            OwnOrBorrow::RefCell(ref_cell) => Ok(OwnOrBorrow::RefCell(ref_cell)),
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(ref_cell) => Ok(OwnOrBorrow::RefCellRc(ref_cell)),
+            other => Err(other),
+        }
+    }
+
+    /// Returns the [`RefCell`] holding the value, moving an owned value into an owned
+    /// [`RefCell`] first.
+    ///
+    /// This allows handing out aliased [`OwnOrBorrow::RefCellRef`] wrappers for a value that
+    /// started out owned. Plain references cannot be converted and return `None`.
+    ///
+    /// The owned value is moved out using a temporary [`Default::default()`] placeholder,
+    /// which is dropped right away.
+    ///
+    /// ```
+    /// use own_or_borrow::OwnOrBorrow;
+    ///
+    /// let mut value = OwnOrBorrow::own(1);
+    /// let cell = value.make_shared().expect("value is owned");
+    ///
+    /// let mut first = OwnOrBorrow::from(cell);
+    /// let mut second = OwnOrBorrow::from(cell);
+    /// *first.borrow_mut() += 1;
+    /// *second.borrow_mut() += 1;
+    ///
+    /// assert_eq!(*value.borrow(), 3);
+    /// ```
+    pub fn make_shared(&mut self) -> Option<&RefCell<T>>
+    where
+        T: Default,
+    {
+        if let OwnOrBorrow::Owned(value) = self {
+            let value = core::mem::take(value);
+            *self = OwnOrBorrow::RefCell(RefCell::new(value));
+        }
+        match self {
+            OwnOrBorrow::RefCell(ref_cell) => Some(ref_cell),
+            OwnOrBorrow::RefCellRef(ref_cell) => Some(ref_cell),
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(ref_cell) => Some(ref_cell),
+            OwnOrBorrow::Owned(_) | OwnOrBorrow::Borrowed(_) | OwnOrBorrow::BorrowedMut(_) => None,
+        }
+    }
+}
+
+impl<'a, T> OwnOrBorrow<'a, T>
+where
+    T: Clone,
+{
+    /// Extracts the owned value, cloning it if it is borrowed.
+    ///
+    /// Owned values, owned [`RefCell`] instances and uniquely owned `Rc` instances are
+    /// moved out without cloning.
+    ///
+    /// ## Panics
+    /// Panics if the value is a [`RefCell`] reference that is currently mutably borrowed.
+    #[track_caller]
+    pub fn into_owned(self) -> T {
+        match self {
+            OwnOrBorrow::Owned(value) => value,
+            OwnOrBorrow::RefCell(ref_cell) => ref_cell.into_inner(),
+            OwnOrBorrow::RefCellRef(ref_cell) => {
+                audit::borrow(Wrapper::OwnOrBorrow, ref_cell, Source::RefCellRef).clone()
+            }
+            OwnOrBorrow::Borrowed(value) => value.clone(),
+            OwnOrBorrow::BorrowedMut(value) => value.clone(),
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(ref_cell) => match Rc::try_unwrap(ref_cell) {
+                Ok(ref_cell) => ref_cell.into_inner(),
+                Err(ref_cell) => {
+                    let value =
+                        audit::borrow(Wrapper::OwnOrBorrow, &ref_cell, Source::RefCellRc).clone();
+                    value
+                }
+            },
+        }
+    }
+
+    /// Creates an owning copy of the value.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn to_owned(&self) -> Owned<T> {
+        let value = match self {
+            OwnOrBorrow::Owned(value) => value.clone(),
+            OwnOrBorrow::RefCell(ref_cell) => {
+                audit::borrow(Wrapper::OwnOrBorrow, ref_cell, Source::RefCell).clone()
+            }
+            OwnOrBorrow::RefCellRef(ref_cell) => {
+                audit::borrow(Wrapper::OwnOrBorrow, ref_cell, Source::RefCellRef).clone()
+            }
+            OwnOrBorrow::Borrowed(value) => T::clone(value),
+            OwnOrBorrow::BorrowedMut(value) => T::clone(value),
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(ref_cell) => {
+                audit::borrow(Wrapper::OwnOrBorrow, ref_cell, Source::RefCellRc).clone()
+            }
+        };
+        OwnOrBorrow::Owned(value)
+    }
+
+    /// Converts the wrapper into a [`Cow`].
+    ///
+    /// Plain references become [`Cow::Borrowed`]. All other variants are extracted as with
+    /// [`into_owned`](Self::into_owned) and become [`Cow::Owned`], since a [`RefCell`] guard
+    /// cannot be held by a [`Cow`].
+    ///
+    /// ## Panics
+    /// Panics if the value is a [`RefCell`] reference that is currently mutably borrowed.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[track_caller]
+    pub fn into_cow(self) -> Cow<'a, T> {
+        match self {
+            OwnOrBorrow::Borrowed(value) => Cow::Borrowed(value),
+            OwnOrBorrow::BorrowedMut(value) => Cow::Borrowed(value),
+            other => Cow::Owned(other.into_owned()),
+        }
+    }
+}
+
+impl<'a, T> Default for OwnOrBorrow<'a, T>
+where
+    T: Default,
+{
+    /// Creates an [`OwnOrBorrow::Owned`] default value.
+    fn default() -> Self {
+        Self::Owned(T::default())
+    }
+}
+
+impl<'a, T> Clone for OwnOrBorrow<'a, T>
+where
+    T: Clone,
+{
+    /// Clones the wrapper.
+    ///
+    /// Owned values and owned [`RefCell`] instances are deep-cloned, while [`RefCell`] and
+    /// shared references are copied and `Rc` instances share the same cell. Since a mutable
+    /// reference cannot be copied, [`OwnOrBorrow::BorrowedMut`] values are cloned into
+    /// [`OwnOrBorrow::Owned`].
+    ///
+    /// ## Panics
+    /// Panics if an owned [`RefCell`] is currently mutably borrowed.
+    fn clone(&self) -> Self {
+        match self {
+            OwnOrBorrow::Owned(value) => OwnOrBorrow::Owned(value.clone()),
+            OwnOrBorrow::RefCell(ref_cell) => OwnOrBorrow::RefCell(RefCell::new(
+                audit::borrow(Wrapper::OwnOrBorrow, ref_cell, Source::RefCell).clone(),
+            )),
+            OwnOrBorrow::RefCellRef(ref_cell) => OwnOrBorrow::RefCellRef(ref_cell),
+            OwnOrBorrow::Borrowed(value) => OwnOrBorrow::Borrowed(value),
+            OwnOrBorrow::BorrowedMut(value) => OwnOrBorrow::Owned(T::clone(value)),
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(ref_cell) => OwnOrBorrow::RefCellRc(ref_cell.clone()),
         }
     }
 }
@@ -126,104 +849,503 @@ impl<'a, T> From<&'a RefCell<T>> for OwnOrBorrow<'a, T> {
     }
 }
 
-impl<'a, T> Deref for Reference<'a, T> {
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T> From<Rc<RefCell<T>>> for OwnOrBorrow<'a, T> {
+    #[inline]
+    fn from(value: Rc<RefCell<T>>) -> Self {
+        Self::RefCellRc(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T> From<Cow<'a, T>> for OwnOrBorrow<'a, T>
+where
+    T: Clone,
+{
+    /// Converts a [`Cow::Borrowed`] into [`OwnOrBorrow::Borrowed`] and a [`Cow::Owned`] into
+    /// [`OwnOrBorrow::Owned`].
+    #[inline]
+    fn from(value: Cow<'a, T>) -> Self {
+        match value {
+            Cow::Borrowed(value) => Self::Borrowed(value),
+            Cow::Owned(value) => Self::Owned(value),
+        }
+    }
+}
+
+impl<'a, T> From<&'a mut T> for OwnOrBorrow<'a, T> {
+    #[inline]
+    fn from(value: &'a mut T) -> Self {
+        Self::BorrowedMut(value)
+    }
+}
+
+impl<'a, T: ?Sized> Reference<'a, T> {
+    /// Copies a [`Reference`], adding another shared borrow for [`RefCell`] guards.
+    ///
+    /// This is an associated function that needs to be used as `Reference::clone(...)`, just
+    /// like [`Ref::clone`]; a [`Clone`] implementation would interfere with `r.borrow().clone()`
+    /// cloning the inner value.
+    ///
+    /// ```
+    /// use own_or_borrow::{OwnOrBorrow, Reference};
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(42);
+    /// let value = OwnOrBorrow::from(&cell);
+    /// let first = value.borrow();
+    /// let second = Reference::clone(&first);
+    /// drop(first);
+    /// assert!(cell.try_borrow_mut().is_err());
+    /// assert_eq!(*second, 42);
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn clone(orig: &Reference<'a, T>) -> Reference<'a, T> {
+        match orig {
+            Reference::Borrowed(value) => Reference::Borrowed(value),
+            Reference::RefCell(cell) => Reference::RefCell(Ref::clone(cell)),
+            Reference::RefCellRef(cell, ref_cell) => {
+                Reference::RefCellRef(Ref::clone(cell), ref_cell)
+            }
+        }
+    }
+
+    /// Makes a new [`Reference`] for a component of the borrowed data.
+    ///
+    /// This is an associated function that needs to be used as `Reference::map(...)`, just
+    /// like [`Ref::map`]; a method would shadow methods of the same name on the inner value.
+    ///
+    /// ```
+    /// use own_or_borrow::{OwnOrBorrow, Reference};
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new((5, 'b'));
+    /// let value = OwnOrBorrow::from(&cell);
+    /// let second = Reference::map(value.borrow(), |t| &t.1);
+    /// assert_eq!(*second, 'b');
+    /// ```
+    pub fn map<U, F>(orig: Reference<'a, T>, f: F) -> Reference<'a, U>
+    where
+        U: ?Sized,
+        F: FnOnce(&T) -> &U,
+    {
+        match orig {
+            Reference::Borrowed(value) => Reference::Borrowed(f(value)),
+            Reference::RefCell(cell) | Reference::RefCellRef(cell, _) => {
+                Reference::RefCell(Ref::map(cell, f))
+            }
+        }
+    }
+
+    /// Makes a new [`Reference`] for an optional component of the borrowed data. The
+    /// original guard is returned as an `Err(..)` if the closure returns `None`.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `Reference::filter_map(...)`, just like [`Ref::filter_map`].
+    ///
+    /// ```
+    /// use own_or_borrow::{OwnOrBorrow, Reference};
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(vec![1, 2, 3]);
+    /// let value = OwnOrBorrow::from(&cell);
+    /// let Ok(second) = Reference::filter_map(value.borrow(), |v| v.get(1)) else {
+    ///     unreachable!();
+    /// };
+    /// assert_eq!(*second, 2);
+    /// ```
+    pub fn filter_map<U, F>(orig: Reference<'a, T>, f: F) -> Result<Reference<'a, U>, Self>
+    where
+        U: ?Sized,
+        F: FnOnce(&T) -> Option<&U>,
+    {
+        match orig {
+            Reference::Borrowed(value) => match f(value) {
+                Some(value) => Ok(Reference::Borrowed(value)),
+                None => Err(Reference::Borrowed(value)),
+            },
+            Reference::RefCell(cell) => Ref::filter_map(cell, f)
+                .map(Reference::RefCell)
+                .map_err(Reference::RefCell),
+            Reference::RefCellRef(cell, ref_cell) => Ref::filter_map(cell, f)
+                .map(Reference::RefCell)
+                .map_err(|cell| Reference::RefCellRef(cell, ref_cell)),
+        }
+    }
+
+    /// Splits a [`Reference`] into multiple [`Reference`]s for different components of the
+    /// borrowed data.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `Reference::map_split(...)`, just like [`Ref::map_split`].
+    ///
+    /// ```
+    /// use own_or_borrow::{OwnOrBorrow, Reference};
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new([1, 2, 3, 4]);
+    /// let value = OwnOrBorrow::from(&cell);
+    /// let (begin, end) = Reference::map_split(value.borrow(), |slice| slice.split_at(2));
+    /// assert_eq!(*begin, [1, 2]);
+    /// assert_eq!(*end, [3, 4]);
+    /// ```
+    pub fn map_split<U, V, F>(orig: Reference<'a, T>, f: F) -> (Reference<'a, U>, Reference<'a, V>)
+    where
+        U: ?Sized,
+        V: ?Sized,
+        F: FnOnce(&T) -> (&U, &V),
+    {
+        match orig {
+            Reference::Borrowed(value) => {
+                let (first, second) = f(value);
+                (Reference::Borrowed(first), Reference::Borrowed(second))
+            }
+            Reference::RefCell(cell) | Reference::RefCellRef(cell, _) => {
+                let (first, second) = Ref::map_split(cell, f);
+                (Reference::RefCell(first), Reference::RefCell(second))
+            }
+        }
+    }
+
+    /// Attempts to turn the shared borrow into a mutable one.
+    ///
+    /// This only succeeds for [`Reference::RefCellRef`] guards, as returned when borrowing a
+    /// [`RefCell`] through an [`OwnOrBorrow`], and only if no other shared borrows of the
+    /// [`RefCell`] exist. The original guard is returned as an `Err(..)` otherwise.
+    ///
+    /// This is an associated function that needs to be used as `Reference::try_upgrade(...)`.
+    ///
+    /// ```
+    /// use own_or_borrow::{OwnOrBorrow, Reference};
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(42);
+    /// let value = OwnOrBorrow::from(&cell);
+    /// let Ok(mut guard) = Reference::try_upgrade(value.borrow()) else {
+    ///     unreachable!();
+    /// };
+    /// *guard += 1;
+    /// ```
+    pub fn try_upgrade(orig: Reference<'a, T>) -> Result<ReferenceMut<'a, T>, Self> {
+        match orig {
+            Reference::RefCellRef(cell, ref_cell) => {
+                drop(cell);
+                match ref_cell.try_borrow_mut() {
+                    Ok(cell) => Ok(ReferenceMut::RefCellRef(cell, ref_cell)),
+                    Err(_) => Err(Reference::RefCellRef(ref_cell.borrow(), ref_cell)),
+                }
+            }
+            other => Err(other),
+        }
+    }
+
+    /// Returns the plain reference of a [`Reference::Borrowed`] guard for the full lifetime
+    /// `'a`. Other guards are returned as an `Err(..)`.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `Reference::try_into_borrowed(...)`. A `TryInto<&'a T>` implementation would conflict
+    /// with the blanket implementation in `core`.
+    pub fn try_into_borrowed(orig: Reference<'a, T>) -> Result<&'a T, Self> {
+        match orig {
+            Reference::Borrowed(value) => Ok(value),
+            other => Err(other),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> ReferenceMut<'a, T> {
+    /// Makes a new [`ReferenceMut`] for a component of the borrowed data.
+    ///
+    /// This is an associated function that needs to be used as `ReferenceMut::map(...)`, just
+    /// like [`RefMut::map`]; a method would shadow methods of the same name on the inner value.
+    ///
+    /// ```
+    /// use own_or_borrow::{OwnOrBorrow, ReferenceMut};
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new((5, 'b'));
+    /// let mut value = OwnOrBorrow::from(&cell);
+    /// {
+    ///     let mut first = ReferenceMut::map(value.borrow_mut(), |t| &mut t.0);
+    ///     *first = 42;
+    /// }
+    /// assert_eq!(*cell.borrow(), (42, 'b'));
+    /// ```
+    pub fn map<U, F>(orig: ReferenceMut<'a, T>, f: F) -> ReferenceMut<'a, U>
+    where
+        U: ?Sized,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        match orig {
+            ReferenceMut::Borrowed(value) => ReferenceMut::Borrowed(f(value)),
+            ReferenceMut::RefCell(cell) | ReferenceMut::RefCellRef(cell, _) => {
+                ReferenceMut::RefCell(RefMut::map(cell, f))
+            }
+        }
+    }
+
+    /// Makes a new [`ReferenceMut`] for an optional component of the borrowed data.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `ReferenceMut::filter_map(...)`, similar to [`RefMut::filter_map`]. Unlike the latter,
+    /// the original guard is dropped if the closure returns `None`, as a plain mutable
+    /// reference that was passed to the closure cannot be handed back without `unsafe` code.
+    ///
+    /// ```
+    /// use own_or_borrow::{OwnOrBorrow, ReferenceMut};
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(vec![1, 2, 3]);
+    /// let mut value = OwnOrBorrow::from(&cell);
+    /// if let Some(mut second) = ReferenceMut::filter_map(value.borrow_mut(), |v| v.get_mut(1)) {
+    ///     *second = 42;
+    /// }
+    /// assert_eq!(*cell.borrow(), [1, 42, 3]);
+    /// ```
+    pub fn filter_map<U, F>(orig: ReferenceMut<'a, T>, f: F) -> Option<ReferenceMut<'a, U>>
+    where
+        U: ?Sized,
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        match orig {
+            ReferenceMut::Borrowed(value) => f(value).map(ReferenceMut::Borrowed),
+            ReferenceMut::RefCell(cell) | ReferenceMut::RefCellRef(cell, _) => {
+                RefMut::filter_map(cell, f).ok().map(ReferenceMut::RefCell)
+            }
+        }
+    }
+
+    /// Splits a [`ReferenceMut`] into multiple [`ReferenceMut`]s for different components of
+    /// the borrowed data.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `ReferenceMut::map_split(...)`, just like [`RefMut::map_split`].
+    ///
+    /// ```
+    /// use own_or_borrow::{OwnOrBorrow, ReferenceMut};
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new([1, 2, 3, 4]);
+    /// let mut value = OwnOrBorrow::from(&cell);
+    /// {
+    ///     let (mut begin, mut end) =
+    ///         ReferenceMut::map_split(value.borrow_mut(), |slice| slice.split_at_mut(2));
+    ///     begin[0] = 10;
+    ///     end[0] = 30;
+    /// }
+    /// assert_eq!(*cell.borrow(), [10, 2, 30, 4]);
+    /// ```
+    pub fn map_split<U, V, F>(
+        orig: ReferenceMut<'a, T>,
+        f: F,
+    ) -> (ReferenceMut<'a, U>, ReferenceMut<'a, V>)
+    where
+        U: ?Sized,
+        V: ?Sized,
+        F: FnOnce(&mut T) -> (&mut U, &mut V),
+    {
+        match orig {
+            ReferenceMut::Borrowed(value) => {
+                let (first, second) = f(value);
+                (
+                    ReferenceMut::Borrowed(first),
+                    ReferenceMut::Borrowed(second),
+                )
+            }
+            ReferenceMut::RefCell(cell) | ReferenceMut::RefCellRef(cell, _) => {
+                let (first, second) = RefMut::map_split(cell, f);
+                (ReferenceMut::RefCell(first), ReferenceMut::RefCell(second))
+            }
+        }
+    }
+
+    /// Turns the mutable borrow into a shared one.
+    ///
+    /// For [`ReferenceMut::RefCellRef`] guards, as returned when borrowing a [`RefCell`]
+    /// through an [`OwnOrBorrow`], the mutable borrow is released and a shared borrow is
+    /// acquired in one step, so no other mutable borrow can slip in between.
+    ///
+    /// Projected [`ReferenceMut::RefCell`] guards do not know their [`RefCell`] and cannot be
+    /// downgraded, as [`RefMut`] offers no way to do so; they are returned as an `Err(..)`.
+    ///
+    /// This is an associated function that needs to be used as `ReferenceMut::downgrade(...)`.
+    ///
+    /// ```
+    /// use own_or_borrow::{OwnOrBorrow, ReferenceMut};
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(42);
+    /// let mut value = OwnOrBorrow::from(&cell);
+    /// let mut guard = value.borrow_mut();
+    /// *guard += 1;
+    ///
+    /// let Ok(shared) = ReferenceMut::downgrade(guard) else {
+    ///     unreachable!();
+    /// };
+    /// assert_eq!(*shared, 43);
+    /// assert_eq!(*cell.borrow(), 43);
+    /// ```
+    pub fn downgrade(orig: ReferenceMut<'a, T>) -> Result<Reference<'a, T>, Self> {
+        match orig {
+            ReferenceMut::Borrowed(value) => Ok(Reference::Borrowed(value)),
+            ReferenceMut::RefCell(cell) => Err(ReferenceMut::RefCell(cell)),
+            ReferenceMut::RefCellRef(cell, ref_cell) => {
+                drop(cell);
+                Ok(Reference::RefCellRef(ref_cell.borrow(), ref_cell))
+            }
+        }
+    }
+
+    /// Returns the plain mutable reference of a [`ReferenceMut::Borrowed`] guard for the full
+    /// lifetime `'a`. Other guards are returned as an `Err(..)`.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `ReferenceMut::try_into_borrowed(...)`. A `TryInto<&'a mut T>` implementation would
+    /// conflict with the blanket implementation in `core`.
+    pub fn try_into_borrowed(orig: ReferenceMut<'a, T>) -> Result<&'a mut T, Self> {
+        match orig {
+            ReferenceMut::Borrowed(value) => Ok(value),
+            other => Err(other),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Deref for Reference<'a, T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
         match self {
             Reference::Borrowed(value) => value,
             Reference::RefCell(cell) => cell.deref(),
-            Reference::RefCellRef(cell) => (*cell).deref(),
+            Reference::RefCellRef(cell, _) => cell.deref(),
         }
     }
 }
 
-impl<'a, T> Deref for ReferenceMut<'a, T> {
+impl<'a, T: ?Sized> Deref for ReferenceMut<'a, T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
         match self {
             ReferenceMut::Borrowed(value) => value,
             ReferenceMut::RefCell(cell) => cell.deref(),
-            ReferenceMut::RefCellRef(cell) => cell.deref(),
+            ReferenceMut::RefCellRef(cell, _) => cell.deref(),
         }
     }
 }
 
-impl<'a, T> DerefMut for ReferenceMut<'a, T> {
+impl<'a, T: ?Sized> DerefMut for ReferenceMut<'a, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         match self {
             ReferenceMut::Borrowed(value) => value,
             ReferenceMut::RefCell(cell) => cell.deref_mut(),
-            ReferenceMut::RefCellRef(cell) => cell.deref_mut(),
+            ReferenceMut::RefCellRef(cell, _) => cell.deref_mut(),
         }
     }
 }
 
-impl<'a, T> core::borrow::Borrow<T> for Reference<'a, T> {
+impl<'a, T: ?Sized> core::borrow::Borrow<T> for Reference<'a, T> {
     #[inline]
     fn borrow(&self) -> &T {
         self.deref()
     }
 }
 
-impl<'a, T> core::borrow::Borrow<T> for ReferenceMut<'a, T> {
+impl<'a, T: ?Sized> core::borrow::Borrow<T> for ReferenceMut<'a, T> {
     #[inline]
     fn borrow(&self) -> &T {
         self.deref()
     }
 }
 
-impl<'a, T> core::borrow::BorrowMut<T> for ReferenceMut<'a, T> {
+impl<'a, T: ?Sized> core::borrow::BorrowMut<T> for ReferenceMut<'a, T> {
     #[inline]
     fn borrow_mut(&mut self) -> &mut T {
         self.deref_mut()
     }
 }
 
-impl<'a, T> AsRef<T> for Reference<'a, T> {
+impl<'a, T: ?Sized> AsRef<T> for Reference<'a, T> {
     #[inline]
     fn as_ref(&self) -> &T {
         self.deref()
     }
 }
 
-impl<'a, T> AsRef<T> for ReferenceMut<'a, T> {
+impl<'a, T: ?Sized> AsRef<T> for ReferenceMut<'a, T> {
     #[inline]
     fn as_ref(&self) -> &T {
         self.deref()
     }
 }
 
-impl<'a, T> AsMut<T> for ReferenceMut<'a, T> {
+impl<'a, T: ?Sized> AsMut<T> for ReferenceMut<'a, T> {
     #[inline]
     fn as_mut(&mut self) -> &mut T {
         self.deref_mut()
     }
 }
 
-impl<'a, T> From<&'a T> for Reference<'a, T> {
+/// Forwards indexing to the inner value, e.g. `value.borrow()[3]`. Unlike plain auto-deref,
+/// this also satisfies `Index` bounds in generic code.
+impl<'a, T, I> Index<I> for Reference<'a, T>
+where
+    T: Index<I> + ?Sized,
+{
+    type Output = T::Output;
+
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        self.deref().index(index)
+    }
+}
+
+impl<'a, T, I> Index<I> for ReferenceMut<'a, T>
+where
+    T: Index<I> + ?Sized,
+{
+    type Output = T::Output;
+
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        self.deref().index(index)
+    }
+}
+
+impl<'a, T, I> IndexMut<I> for ReferenceMut<'a, T>
+where
+    T: IndexMut<I> + ?Sized,
+{
+    #[inline]
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        self.deref_mut().index_mut(index)
+    }
+}
+
+impl<'a, T: ?Sized> From<&'a T> for Reference<'a, T> {
     #[inline]
     fn from(value: &'a T) -> Self {
         Reference::Borrowed(value)
     }
 }
 
-impl<'a, T> From<&'a mut T> for ReferenceMut<'a, T> {
+impl<'a, T: ?Sized> From<&'a mut T> for ReferenceMut<'a, T> {
     #[inline]
     fn from(value: &'a mut T) -> Self {
         ReferenceMut::Borrowed(value)
     }
 }
 
-impl<'a, T> From<Ref<'a, T>> for Reference<'a, T> {
+impl<'a, T: ?Sized> From<Ref<'a, T>> for Reference<'a, T> {
     #[inline]
     fn from(value: Ref<'a, T>) -> Self {
         Reference::RefCell(value)
     }
 }
 
-impl<'a, T> From<RefMut<'a, T>> for ReferenceMut<'a, T> {
+impl<'a, T: ?Sized> From<RefMut<'a, T>> for ReferenceMut<'a, T> {
     #[inline]
     fn from(value: RefMut<'a, T>) -> Self {
         ReferenceMut::RefCell(value)
@@ -231,25 +1353,47 @@ impl<'a, T> From<RefMut<'a, T>> for ReferenceMut<'a, T> {
 }
 
 impl<'a, T> TryInto<RefCell<T>> for OwnOrBorrow<'a, T> {
-    type Error = TryIntoError;
+    type Error = TryIntoError<Self>;
 
     fn try_into(self) -> Result<RefCell<T>, Self::Error> {
         match self {
-            OwnOrBorrow::Owned(_) => Err(TryIntoError::NotConvertible),
             OwnOrBorrow::RefCell(cell) => Ok(cell),
-            OwnOrBorrow::RefCellRef(_) => Err(TryIntoError::NotConvertible),
+            other => Err(TryIntoError::NotConvertible(other)),
         }
     }
 }
 
 impl<'a, T> TryInto<&'a RefCell<T>> for OwnOrBorrow<'a, T> {
-    type Error = TryIntoError;
+    type Error = TryIntoError<Self>;
 
     fn try_into(self) -> Result<&'a RefCell<T>, Self::Error> {
         match self {
-            OwnOrBorrow::Owned(_) => Err(TryIntoError::NotConvertible),
-            OwnOrBorrow::RefCell(_) => Err(TryIntoError::NotConvertible),
             OwnOrBorrow::RefCellRef(cell) => Ok(cell),
+            other => Err(TryIntoError::NotConvertible(other)),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> TryInto<Ref<'a, T>> for Reference<'a, T> {
+    type Error = TryIntoError<Self>;
+
+    /// Succeeds for [`Reference::RefCell`] and [`Reference::RefCellRef`] guards.
+    fn try_into(self) -> Result<Ref<'a, T>, Self::Error> {
+        match self {
+            Reference::RefCell(cell) | Reference::RefCellRef(cell, _) => Ok(cell),
+            other => Err(TryIntoError::NotConvertible(other)),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> TryInto<RefMut<'a, T>> for ReferenceMut<'a, T> {
+    type Error = TryIntoError<Self>;
+
+    /// Succeeds for [`ReferenceMut::RefCell`] and [`ReferenceMut::RefCellRef`] guards.
+    fn try_into(self) -> Result<RefMut<'a, T>, Self::Error> {
+        match self {
+            ReferenceMut::RefCell(cell) | ReferenceMut::RefCellRef(cell, _) => Ok(cell),
+            other => Err(TryIntoError::NotConvertible(other)),
         }
     }
 }
@@ -258,10 +1402,13 @@ impl<'a, T> core::fmt::Debug for OwnOrBorrow<'a, T>
 where
     T: core::fmt::Debug,
 {
+    /// Formats the inner value, or prints `<mutably borrowed>` if it is currently mutably
+    /// borrowed.
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let data = self.borrow();
-        let data = data.as_ref();
-        core::fmt::Debug::fmt(data, f)
+        match self.try_borrow() {
+            Ok(data) => core::fmt::Debug::fmt(data.as_ref(), f),
+            Err(_) => f.write_str("<mutably borrowed>"),
+        }
     }
 }
 
@@ -269,23 +1416,13 @@ impl<'a, T> core::fmt::Display for OwnOrBorrow<'a, T>
 where
     T: core::fmt::Display,
 {
+    /// Formats the inner value, or prints `<mutably borrowed>` if it is currently mutably
+    /// borrowed.
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let data = self.borrow();
-        let data = data.as_ref();
-        core::fmt::Display::fmt(data, f)
-    }
-}
-
-#[cfg(feature = "defmt")]
-#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
-impl<'a, T> defmt::Format for OwnOrBorrow<'a, T>
-where
-    T: defmt::Format,
-{
-    fn format(&self, fmt: defmt::Formatter) {
-        let data = self.borrow();
-        let data = data.as_ref();
-        defmt::Format::format(data, fmt)
+        match self.try_borrow() {
+            Ok(data) => core::fmt::Display::fmt(data.as_ref(), f),
+            Err(_) => f.write_str("<mutably borrowed>"),
+        }
     }
 }
 
@@ -301,6 +1438,85 @@ mod tests {
         assert_eq!(value.borrow_mut().as_mut(), &mut 42);
     }
 
+    #[test]
+    fn static_owned_alias() {
+        let cell = RefCell::new(42);
+        let value: Owned<i32> = OwnOrBorrow::from(cell);
+        assert_eq!(value.try_into_owned().ok(), None);
+    }
+
+    #[test]
+    fn into_static_round_trip() {
+        struct Holder {
+            value: Owned<i32>,
+        }
+
+        fn detach(value: OwnOrBorrow<'_, i32>) -> Option<Owned<i32>> {
+            value.into_static().ok()
+        }
+
+        let mut holder = Holder {
+            value: detach(OwnOrBorrow::own(41)).expect("value is owned"),
+        };
+        *holder.value.borrow_mut() += 1;
+        assert!(matches!(holder.value, OwnOrBorrow::Owned(42)));
+
+        let value = detach(OwnOrBorrow::from(RefCell::new(42))).expect("cell is owned");
+        assert!(matches!(value, OwnOrBorrow::RefCell(_)));
+
+        #[cfg(feature = "alloc")]
+        {
+            let value = detach(OwnOrBorrow::RefCellRc(Rc::new(RefCell::new(42))));
+            assert!(matches!(value, Some(OwnOrBorrow::RefCellRc(_))));
+        }
+
+        let cell = RefCell::new(42);
+        let value = OwnOrBorrow::from(&cell).into_static();
+        assert!(matches!(value, Err(OwnOrBorrow::RefCellRef(_))));
+    }
+
+    #[test]
+    fn const_constructors() {
+        const OWNED: OwnOrBorrow<'static, i32> = OwnOrBorrow::own(1);
+        static SHARED: i32 = 3;
+        const BORROWED: OwnOrBorrow<'static, i32> = OwnOrBorrow::borrowed(&SHARED);
+
+        assert!(matches!(OWNED, OwnOrBorrow::Owned(1)));
+        let cell: OwnOrBorrow<'_, i32> = const { OwnOrBorrow::new_refcell(2) };
+        assert!(matches!(cell, OwnOrBorrow::RefCell(_)));
+        assert_eq!(*BORROWED.borrow(), 3);
+
+        let cell = RefCell::new(4);
+        let value = OwnOrBorrow::from_refcell_ref(&cell);
+        assert!(matches!(value, OwnOrBorrow::RefCellRef(_)));
+    }
+
+    #[test]
+    fn index_guards() {
+        fn first<G: Index<usize, Output = u8>>(guard: G) -> u8 {
+            guard[0]
+        }
+
+        let cell = RefCell::new([1u8, 2, 3, 4]);
+        let mut value = OwnOrBorrow::from(&cell);
+        value.borrow_mut()[0] = 9;
+        assert_eq!(value.borrow()[3], 4);
+        assert_eq!(value.borrow()[1..3], [2, 3]);
+        assert_eq!(first(value.borrow()), 9);
+        assert_eq!(first(value.borrow_mut()), 9);
+    }
+
+    #[test]
+    fn sequential_borrows() {
+        let cell = RefCell::new(42);
+        let mut value = OwnOrBorrow::from(&cell);
+        *value.borrow_mut() += 1;
+        *value.borrow_mut() += 1;
+        assert_eq!(value.borrow().as_ref(), &44);
+        *value.try_borrow_mut().expect("failed to borrow") += 1;
+        assert_eq!(value.try_borrow().expect("failed to borrow").as_ref(), &45);
+    }
+
     #[test]
     fn borrow_reference() {
         let value = &42;
@@ -317,6 +1533,50 @@ mod tests {
         assert_eq!(value.borrow_mut().as_mut(), &mut 42);
     }
 
+    #[test]
+    fn borrow_plain_reference() {
+        let data = 42;
+        let mut value = OwnOrBorrow::borrowed(&data);
+        assert_eq!(value.borrow().as_ref(), &42);
+        assert!(matches!(value.try_borrow_mut(), Err(BorrowFault::Shared)));
+    }
+
+    #[test]
+    #[should_panic(expected = "already mutably borrowed (referenced RefCell)")]
+    fn borrow_conflict_names_the_storage() {
+        let cell = RefCell::new(42);
+        let value = OwnOrBorrow::from(&cell);
+        let _guard = cell.borrow_mut();
+        let _ = value.borrow();
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(expected = "already borrowed (reference-counted RefCell)")]
+    fn borrow_mut_conflict_names_the_storage() {
+        let cell = Rc::new(RefCell::new(42));
+        let mut value = OwnOrBorrow::from(cell.clone());
+        let _guard = cell.borrow();
+        let _ = value.borrow_mut();
+    }
+
+    #[test]
+    #[should_panic]
+    fn borrow_mut_plain_reference_panics() {
+        let data = 42;
+        let mut value = OwnOrBorrow::borrowed(&data);
+        let _ = value.borrow_mut();
+    }
+
+    #[test]
+    fn borrow_plain_mutable_reference() {
+        let mut data = 42;
+        let mut value = OwnOrBorrow::from(&mut data);
+        assert_eq!(value.borrow().as_ref(), &42);
+        *value.borrow_mut() = 17;
+        assert_eq!(data, 17);
+    }
+
     #[test]
     fn deref_refcell() {
         let value = RefCell::new(42);
@@ -325,6 +1585,149 @@ mod tests {
         assert_eq!(value.borrow_mut().deref_mut(), &mut 42);
     }
 
+    #[test]
+    fn try_borrow_owned_type() {
+        let mut value = OwnOrBorrow::own(42);
+        assert_eq!(value.try_borrow().expect("failed to borrow").as_ref(), &42);
+        assert_eq!(
+            value.try_borrow_mut().expect("failed to borrow").as_mut(),
+            &mut 42
+        );
+    }
+
+    #[test]
+    fn try_borrow_fails_while_mutably_borrowed() {
+        let cell = RefCell::new(42);
+        let value = OwnOrBorrow::from(&cell);
+        let _guard = cell.borrow_mut();
+        assert!(matches!(value.try_borrow(), Err(BorrowFault::Borrow(_))));
+    }
+
+    #[test]
+    fn try_borrow_mut_fails_while_borrowed() {
+        let cell = RefCell::new(42);
+        let mut value = OwnOrBorrow::from(&cell);
+        let _guard = cell.borrow();
+        assert!(matches!(
+            value.try_borrow_mut(),
+            Err(BorrowFault::BorrowMut(_))
+        ));
+    }
+
+    #[test]
+    fn map_reference() {
+        let value = OwnOrBorrow::own((1, 2));
+        assert_eq!(*Reference::map(value.borrow(), |t| &t.1), 2);
+
+        let value = OwnOrBorrow::from(RefCell::new((1, 2)));
+        let first = Reference::map(value.borrow(), |t| &t.0);
+        assert!(matches!(first, Reference::RefCell(_)));
+        assert_eq!(*first, 1);
+    }
+
+    #[test]
+    fn map_reference_mut() {
+        let mut data = (1, 2);
+        let mut value = OwnOrBorrow::from(&mut data);
+        *ReferenceMut::map(value.borrow_mut(), |t| &mut t.1) = 3;
+        assert_eq!(data, (1, 3));
+
+        let cell = RefCell::new((1, 2));
+        let mut value = OwnOrBorrow::from(&cell);
+        let first = ReferenceMut::map(value.borrow_mut(), |t| &mut t.0);
+        assert!(matches!(first, ReferenceMut::RefCell(_)));
+        assert!(cell.try_borrow().is_err());
+    }
+
+    #[test]
+    fn downgrade_and_upgrade() {
+        let cell = RefCell::new(1);
+        let mut value = OwnOrBorrow::from(&cell);
+        let mut guard = value.borrow_mut();
+        *guard += 1;
+        let Ok(shared) = ReferenceMut::downgrade(guard) else {
+            panic!("failed to downgrade");
+        };
+        assert!(cell.try_borrow().is_ok());
+        assert!(cell.try_borrow_mut().is_err());
+
+        let other = cell.borrow();
+        let Err(shared) = Reference::try_upgrade(shared) else {
+            panic!("upgraded while shared");
+        };
+        drop(other);
+        let Ok(mut guard) = Reference::try_upgrade(shared) else {
+            panic!("failed to upgrade");
+        };
+        *guard += 1;
+        drop(guard);
+        assert_eq!(*cell.borrow(), 3);
+    }
+
+    #[test]
+    fn downgrade_projected_guard_fails() {
+        let cell = RefCell::new((1, 2));
+        let mut value = OwnOrBorrow::from(&cell);
+        let first = ReferenceMut::map(value.borrow_mut(), |t| &mut t.0);
+        assert!(ReferenceMut::downgrade(first).is_err());
+
+        let mut data = 1;
+        let mut value = OwnOrBorrow::from(&mut data);
+        assert!(ReferenceMut::downgrade(value.borrow_mut()).is_ok());
+        assert!(Reference::try_upgrade(value.borrow()).is_err());
+    }
+
+    #[test]
+    fn filter_map_reference() {
+        let value = OwnOrBorrow::own([1, 2]);
+        let Err(original) = Reference::filter_map(value.borrow(), |a| a.get(5)) else {
+            panic!("failed to fail");
+        };
+        assert_eq!(*original, [1, 2]);
+
+        let cell = RefCell::new([1, 2]);
+        let value = OwnOrBorrow::from(&cell);
+        let Ok(second) = Reference::filter_map(value.borrow(), |a| a.get(1)) else {
+            panic!("failed to map");
+        };
+        assert!(matches!(second, Reference::RefCell(_)));
+        assert_eq!(*second, 2);
+    }
+
+    #[test]
+    fn filter_map_reference_mut_releases_borrow() {
+        let cell = RefCell::new([1, 2]);
+        let mut value = OwnOrBorrow::from(&cell);
+        assert!(ReferenceMut::filter_map(value.borrow_mut(), |a| a.get_mut(5)).is_none());
+        assert!(cell.try_borrow_mut().is_ok());
+
+        let mut data = [1, 2];
+        let mut value = OwnOrBorrow::from(&mut data);
+        *ReferenceMut::filter_map(value.borrow_mut(), |a| a.get_mut(0)).expect("failed to map") = 3;
+        assert_eq!(data, [3, 2]);
+    }
+
+    #[test]
+    fn map_split_reference() {
+        let value = OwnOrBorrow::own((1, 'a'));
+        let (first, second) = Reference::map_split(value.borrow(), |t| (&t.0, &t.1));
+        assert_eq!((*first, *second), (1, 'a'));
+    }
+
+    #[test]
+    fn map_split_reference_mut() {
+        let cell = RefCell::new((1, 'a'));
+        let mut value = OwnOrBorrow::from(&cell);
+        {
+            let (mut first, second) =
+                ReferenceMut::map_split(value.borrow_mut(), |t| (&mut t.0, &mut t.1));
+            drop(second);
+            *first = 2;
+            assert!(cell.try_borrow().is_err());
+        }
+        assert_eq!(*cell.borrow(), (2, 'a'));
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn debug() {
@@ -341,6 +1744,18 @@ mod tests {
         assert_eq!(format!("{}", value), "42");
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn format_while_mutably_borrowed() {
+        let cell = RefCell::new(42);
+        let value = OwnOrBorrow::from(&cell);
+        let guard = cell.borrow_mut();
+        assert_eq!(format!("{:?}", value), "<mutably borrowed>");
+        assert_eq!(format!("{}", value), "<mutably borrowed>");
+        drop(guard);
+        assert_eq!(format!("{}", value), "42");
+    }
+
     #[test]
     fn try_into_owned() {
         let value = OwnOrBorrow::own(42);
@@ -364,6 +1779,278 @@ mod tests {
         value.try_into_owned().expect_err("failed to fail");
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn from_cow() {
+        let data = 42;
+        assert!(matches!(
+            OwnOrBorrow::from(Cow::Borrowed(&data)),
+            OwnOrBorrow::Borrowed(&42)
+        ));
+        assert!(matches!(
+            OwnOrBorrow::from(Cow::<i32>::Owned(17)),
+            OwnOrBorrow::Owned(17)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn into_cow() {
+        let data = 42;
+        assert!(matches!(
+            OwnOrBorrow::borrowed(&data).into_cow(),
+            Cow::Borrowed(&42)
+        ));
+
+        let cell = RefCell::new(17);
+        assert!(matches!(
+            OwnOrBorrow::from(&cell).into_cow(),
+            Cow::Owned(17)
+        ));
+    }
+
+    #[test]
+    fn default_is_owned() {
+        let value = OwnOrBorrow::<'_, i32>::default();
+        assert!(matches!(value, OwnOrBorrow::Owned(0)));
+    }
+
+    #[test]
+    fn clone_deep_clones_owned_cells() {
+        let value = OwnOrBorrow::from(RefCell::new(42));
+        let mut clone = value.clone();
+        *clone.borrow_mut() += 1;
+        assert_eq!(value.borrow().as_ref(), &42);
+        assert_eq!(clone.borrow().as_ref(), &43);
+    }
+
+    #[test]
+    fn clone_copies_references() {
+        let cell = RefCell::new(42);
+        let value = OwnOrBorrow::from(&cell);
+        let mut clone = value.clone();
+        *clone.borrow_mut() += 1;
+        assert_eq!(value.borrow().as_ref(), &43);
+
+        let mut data = 1;
+        let value = OwnOrBorrow::from(&mut data);
+        assert!(matches!(value.clone(), OwnOrBorrow::Owned(1)));
+    }
+
+    #[test]
+    fn inspect_variants() {
+        let cell = RefCell::new(42);
+        let owned = OwnOrBorrow::own(42);
+        let borrowed = OwnOrBorrow::from(&cell);
+
+        assert!(owned.is_owned());
+        assert!(!owned.is_borrowed());
+        assert_eq!(owned.as_owned(), Some(&42));
+        assert!(owned.as_refcell().is_none());
+
+        assert!(!borrowed.is_owned());
+        assert!(borrowed.is_borrowed());
+        assert!(borrowed.as_owned().is_none());
+        assert!(core::ptr::eq(borrowed.as_refcell().unwrap(), &cell));
+    }
+
+    #[test]
+    fn borrow_state_tracks_refcell() {
+        let cell = RefCell::new(42);
+        let value = OwnOrBorrow::from(&cell);
+        assert_eq!(value.borrow_state(), BorrowState::Unused);
+        {
+            let _guard = cell.borrow();
+            assert_eq!(value.borrow_state(), BorrowState::Reading);
+        }
+        {
+            let _guard = cell.borrow_mut();
+            assert_eq!(value.borrow_state(), BorrowState::Writing);
+        }
+        assert_eq!(OwnOrBorrow::own(42).borrow_state(), BorrowState::Untracked);
+    }
+
+    #[test]
+    fn get_mut_skips_runtime_checks() {
+        let mut value = OwnOrBorrow::from(RefCell::new(42));
+        *value.get_mut().expect("failed to get value") += 1;
+        assert_eq!(value.borrow().as_ref(), &43);
+
+        let mut data = 1;
+        let mut value = OwnOrBorrow::from(&mut data);
+        *value.get_mut().expect("failed to get value") += 1;
+        assert_eq!(data, 2);
+    }
+
+    #[test]
+    fn get_mut_fails_for_references() {
+        let cell = RefCell::new(42);
+        assert!(OwnOrBorrow::from(&cell).get_mut().is_none());
+        assert!(OwnOrBorrow::borrowed(&42).get_mut().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn get_mut_requires_unique_rc() {
+        let shared = Rc::new(RefCell::new(42));
+        let mut value = OwnOrBorrow::from(shared.clone());
+        assert!(value.get_mut().is_none());
+        drop(shared);
+        assert_eq!(value.get_mut(), Some(&mut 42));
+    }
+
+    #[test]
+    fn with_returns_closure_result() {
+        let cell = RefCell::new(42);
+        let value = OwnOrBorrow::from(&cell);
+        assert_eq!(value.with(|value| *value + 1), 43);
+        assert!(cell.try_borrow_mut().is_ok());
+    }
+
+    #[test]
+    fn with_mut_modifies_all_variants() {
+        let cell = RefCell::new(1);
+        let mut data = 2;
+        let mut values = [
+            OwnOrBorrow::own(0),
+            OwnOrBorrow::from(&cell),
+            OwnOrBorrow::from(&mut data),
+        ];
+        for value in values.iter_mut() {
+            value.with_mut(|value| *value *= 10);
+        }
+        assert_eq!(values[0].with(|value| *value), 0);
+        assert_eq!(values[1].with(|value| *value), 10);
+        assert_eq!(*cell.borrow(), 10);
+        assert_eq!(data, 20);
+    }
+
+    #[test]
+    #[should_panic]
+    fn with_mut_panics_for_shared_reference() {
+        let mut value = OwnOrBorrow::borrowed(&42);
+        value.with_mut(|value| *value += 1);
+    }
+
+    #[test]
+    fn try_into_owned_returns_rejected_value() {
+        let cell = RefCell::new(42);
+        let value = OwnOrBorrow::from(&cell);
+        let value = value
+            .try_into_owned()
+            .expect_err("failed to fail")
+            .into_inner();
+        assert!(matches!(value, OwnOrBorrow::RefCellRef(_)));
+        assert_eq!(value.borrow().as_ref(), &42);
+    }
+
+    #[test]
+    fn into_owned_clones_borrowed_values() {
+        let cell = RefCell::new(42);
+        assert_eq!(OwnOrBorrow::from(&cell).into_owned(), 42);
+        assert_eq!(OwnOrBorrow::from(RefCell::new(17)).into_owned(), 17);
+        assert_eq!(OwnOrBorrow::borrowed(&3).into_owned(), 3);
+    }
+
+    #[test]
+    fn to_owned_detaches_from_refcell() {
+        let cell = RefCell::new(42);
+        let value = OwnOrBorrow::from(&cell);
+        let copy = value.to_owned();
+        *cell.borrow_mut() = 17;
+        assert!(matches!(copy, OwnOrBorrow::Owned(42)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn borrow_refcell_rc() {
+        let shared = Rc::new(RefCell::new(42));
+        let mut value = OwnOrBorrow::from(shared.clone());
+        *value.borrow_mut() += 1;
+        assert_eq!(*shared.borrow(), 43);
+        assert_eq!(value.borrow().as_ref(), &43);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_unwrap_refcell_rc() {
+        let shared = Rc::new(RefCell::new(42));
+        let value = OwnOrBorrow::from(shared.clone());
+        let value = value.try_unwrap().expect_err("value is still shared");
+        drop(shared);
+        assert_eq!(value.try_unwrap().ok(), Some(42));
+    }
+
+    #[test]
+    fn try_unwrap_owned_refcell() {
+        assert_eq!(
+            OwnOrBorrow::from(RefCell::new(42)).try_unwrap().ok(),
+            Some(42)
+        );
+        let cell = RefCell::new(42);
+        assert!(OwnOrBorrow::from(&cell).try_unwrap().is_err());
+    }
+
+    #[test]
+    fn into_refcell_wraps_owned_values() {
+        let cell = OwnOrBorrow::own(42).into_refcell().expect("value is owned");
+        assert_eq!(cell.into_inner(), 42);
+        let cell = OwnOrBorrow::from(RefCell::new(42))
+            .into_refcell()
+            .expect("cell is owned");
+        assert_eq!(cell.into_inner(), 42);
+        assert!(OwnOrBorrow::borrowed(&42).into_refcell().is_err());
+    }
+
+    #[test]
+    fn make_shared_converts_in_place() {
+        let mut value = OwnOrBorrow::own(1);
+        *value.make_shared().expect("value is owned").borrow_mut() += 1;
+        assert!(matches!(value, OwnOrBorrow::RefCell(_)));
+        assert_eq!(*value.borrow(), 2);
+
+        let mut data = 1;
+        assert!(OwnOrBorrow::from(&mut data).make_shared().is_none());
+    }
+
+    #[test]
+    fn replace_across_variants() {
+        let cell = RefCell::new(1);
+        let mut value = OwnOrBorrow::from(&cell);
+        assert_eq!(value.replace(2), 1);
+        assert_eq!(*cell.borrow(), 2);
+
+        let mut value = OwnOrBorrow::own(1);
+        assert_eq!(value.replace(2), 1);
+        assert!(matches!(value, OwnOrBorrow::Owned(2)));
+    }
+
+    #[test]
+    fn swap_across_variants() {
+        let cell = RefCell::new(1);
+        let mut data = 2;
+        let mut first = OwnOrBorrow::from(&cell);
+        let mut second = OwnOrBorrow::from(&mut data);
+        first.swap(&mut second);
+        assert_eq!(*cell.borrow(), 2);
+        assert_eq!(data, 1);
+    }
+
+    #[test]
+    fn take_leaves_default() {
+        let cell = RefCell::new(42);
+        let mut value = OwnOrBorrow::from(&cell);
+        assert_eq!(value.take(), 42);
+        assert_eq!(*cell.borrow(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn replace_plain_reference_panics() {
+        let data = 42;
+        OwnOrBorrow::borrowed(&data).replace(17);
+    }
+
     #[test]
     fn try_into_from_refcell() {
         let cell = RefCell::new(42);
@@ -393,4 +2080,47 @@ mod tests {
         <OwnOrBorrow<'_, i32> as TryInto<&RefCell<i32>>>::try_into(value)
             .expect_err("failed to fail");
     }
+
+    #[test]
+    fn try_into_refcell_returns_rejected_value() {
+        let value = OwnOrBorrow::own(42);
+        let error = <OwnOrBorrow<'_, i32> as TryInto<RefCell<i32>>>::try_into(value)
+            .expect_err("failed to fail");
+        assert!(matches!(error.into_inner(), OwnOrBorrow::Owned(42)));
+    }
+
+    #[test]
+    fn guards_into_inner_references() {
+        let value = OwnOrBorrow::own(42);
+        let Ok(reference) = Reference::try_into_borrowed(value.borrow()) else {
+            panic!("guard is a reference");
+        };
+        assert_eq!(*reference, 42);
+
+        let cell = RefCell::new(42);
+        let mut value = OwnOrBorrow::from(&cell);
+        let guard: RefMut<'_, i32> = value.borrow_mut().try_into().expect("guard is a RefMut");
+        drop(guard);
+        let guard = Reference::try_into_borrowed(value.borrow()).expect_err("guard is a Ref");
+        assert!(matches!(guard, Reference::RefCellRef(..)));
+        drop(guard);
+        let _guard: Ref<'_, i32> = value.borrow().try_into().expect("guard is a Ref");
+    }
+
+    #[test]
+    fn clone_guards() {
+        let cell = RefCell::new(42);
+        let value = OwnOrBorrow::from(&cell);
+        let first = value.borrow();
+        let second = Reference::clone(&first);
+        drop(first);
+        assert!(matches!(second, Reference::RefCellRef(..)));
+        assert!(cell.try_borrow_mut().is_err());
+        drop(second);
+        assert!(cell.try_borrow_mut().is_ok());
+
+        let value = OwnOrBorrow::own(42);
+        let guard = value.borrow();
+        assert_eq!(*Reference::clone(&guard), 42);
+    }
 }
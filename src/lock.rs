@@ -0,0 +1,806 @@
+//! Thread-safe counterpart to [`OwnOrBorrow`](crate::OwnOrBorrow).
+
+use crate::LockError;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A type that provides either an owned value or a [`RwLock`] or [`Mutex`] protected value.
+///
+/// Unlike [`OwnOrBorrow`](crate::OwnOrBorrow), this type is [`Sync`] whenever `T` is
+/// [`Send`] and [`Sync`], so a single lock can be shared across threads while each user
+/// holds its own wrapper.
+///
+/// [`OwnOrBorrow`](crate::OwnOrBorrow) cannot wrap a lock itself: its guards support
+/// projections such as [`Reference::map`](crate::Reference::map), which the stable `std` lock
+/// guards do not offer. Library functions that should accept either type can take a
+/// [`BorrowBackend`](crate::BorrowBackend) instead.
+///
+/// ```
+/// use own_or_borrow::OwnOrLock;
+/// use std::sync::RwLock;
+///
+/// let lock = RwLock::new(42);
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         let mut value = OwnOrLock::from(&lock);
+///         *value.write().expect("lock poisoned") += 1;
+///     });
+/// });
+///
+/// assert_eq!(*lock.read().unwrap(), 43);
+/// ```
+pub enum OwnOrLock<'a, T> {
+    /// An owned value.
+    Owned(T),
+    /// An owned [`RwLock`].
+    RwLock(RwLock<T>),
+    /// A reference to a [`RwLock`].
+    RwLockRef(&'a RwLock<T>),
+    /// A shared, reference-counted [`RwLock`]. The wrapper keeps the value alive.
+    RwLockArc(Arc<RwLock<T>>),
+    /// An owned [`Mutex`].
+    Mutex(Mutex<T>),
+    /// A reference to a [`Mutex`].
+    MutexRef(&'a Mutex<T>),
+    /// A shared, reference-counted [`Mutex`]. The wrapper keeps the value alive.
+    MutexArc(Arc<Mutex<T>>),
+    /// An owned [`parking_lot::RwLock`].
+    #[cfg(feature = "parking_lot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+    ParkingLotRwLock(parking_lot::RwLock<T>),
+    /// A reference to a [`parking_lot::RwLock`].
+    #[cfg(feature = "parking_lot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+    ParkingLotRwLockRef(&'a parking_lot::RwLock<T>),
+    /// A shared, reference-counted [`parking_lot::RwLock`]. The wrapper keeps the value alive.
+    #[cfg(feature = "parking_lot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+    ParkingLotRwLockArc(Arc<parking_lot::RwLock<T>>),
+    /// An owned [`parking_lot::Mutex`].
+    #[cfg(feature = "parking_lot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+    ParkingLotMutex(parking_lot::Mutex<T>),
+    /// A reference to a [`parking_lot::Mutex`].
+    #[cfg(feature = "parking_lot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+    ParkingLotMutexRef(&'a parking_lot::Mutex<T>),
+    /// A shared, reference-counted [`parking_lot::Mutex`]. The wrapper keeps the value alive.
+    #[cfg(feature = "parking_lot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+    ParkingLotMutexArc(Arc<parking_lot::Mutex<T>>),
+}
+
+/// A reference to owned or lock protected data.
+pub enum LockReference<'a, T> {
+    /// A reference to an owned value.
+    Borrowed(&'a T),
+    /// A [`RwLock`] read guard.
+    RwLock(RwLockReadGuard<'a, T>),
+    /// A [`Mutex`] guard.
+    Mutex(MutexGuard<'a, T>),
+    /// A [`parking_lot::RwLock`] read guard.
+    #[cfg(feature = "parking_lot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+    ParkingLotRwLock(parking_lot::RwLockReadGuard<'a, T>),
+    /// A [`parking_lot::Mutex`] guard.
+    #[cfg(feature = "parking_lot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+    ParkingLotMutex(parking_lot::MutexGuard<'a, T>),
+}
+
+/// A mutable reference to owned or lock protected data.
+pub enum LockReferenceMut<'a, T> {
+    /// A mutable reference to an owned value.
+    Borrowed(&'a mut T),
+    /// A [`RwLock`] write guard.
+    RwLock(RwLockWriteGuard<'a, T>),
+    /// A [`Mutex`] guard.
+    Mutex(MutexGuard<'a, T>),
+    /// A [`parking_lot::RwLock`] write guard.
+    #[cfg(feature = "parking_lot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+    ParkingLotRwLock(parking_lot::RwLockWriteGuard<'a, T>),
+    /// A [`parking_lot::Mutex`] guard.
+    #[cfg(feature = "parking_lot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+    ParkingLotMutex(parking_lot::MutexGuard<'a, T>),
+}
+
+impl<'a, T> OwnOrLock<'a, T> {
+    /// Initializes a new instance that owns data.
+    pub const fn own(value: T) -> Self {
+        Self::Owned(value)
+    }
+
+    /// Locks the inner value for reading.
+    ///
+    /// This blocks the current thread until the lock can be acquired. A [`Mutex`] is
+    /// locked exclusively. The `parking_lot` locks cannot be poisoned and always succeed.
+    pub fn read(&self) -> Result<LockReference<'_, T>, LockError> {
+        match self {
+            OwnOrLock::Owned(value) => Ok(LockReference::Borrowed(value)),
+            OwnOrLock::RwLock(lock) => Ok(lock.read()?.into()),
+            OwnOrLock::RwLockRef(lock) => Ok(lock.read()?.into()),
+            OwnOrLock::RwLockArc(lock) => Ok(lock.read()?.into()),
+            OwnOrLock::Mutex(mutex) => Ok(mutex.lock()?.into()),
+            OwnOrLock::MutexRef(mutex) => Ok(mutex.lock()?.into()),
+            OwnOrLock::MutexArc(mutex) => Ok(mutex.lock()?.into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLock(lock) => Ok(lock.read().into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLockRef(lock) => Ok(lock.read().into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLockArc(lock) => Ok(lock.read().into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutex(mutex) => Ok(mutex.lock().into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutexRef(mutex) => Ok(mutex.lock().into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutexArc(mutex) => Ok(mutex.lock().into()),
+        }
+    }
+
+    /// Locks the inner value for writing.
+    ///
+    /// This blocks the current thread until the lock can be acquired. Owned locks are
+    /// accessed without locking since `&mut self` already guarantees exclusive access.
+    pub fn write(&mut self) -> Result<LockReferenceMut<'_, T>, LockError> {
+        match self {
+            OwnOrLock::Owned(value) => Ok(LockReferenceMut::Borrowed(value)),
+            OwnOrLock::RwLock(lock) => Ok(lock.get_mut()?.into()),
+            OwnOrLock::RwLockRef(lock) => Ok(lock.write()?.into()),
+            OwnOrLock::RwLockArc(lock) => Ok(lock.write()?.into()),
+            OwnOrLock::Mutex(mutex) => Ok(mutex.get_mut()?.into()),
+            OwnOrLock::MutexRef(mutex) => Ok(mutex.lock()?.into()),
+            OwnOrLock::MutexArc(mutex) => Ok(mutex.lock()?.into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLock(lock) => Ok(lock.get_mut().into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLockRef(lock) => Ok(lock.write().into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLockArc(lock) => Ok(lock.write().into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutex(mutex) => Ok(mutex.get_mut().into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutexRef(mutex) => Ok(mutex.lock().into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutexArc(mutex) => Ok(mutex.lock().into()),
+        }
+    }
+
+    /// Attempts to lock the inner value for reading without blocking.
+    ///
+    /// Fails with [`LockError::WouldBlock`] if the lock is currently held exclusively, or with
+    /// [`LockError::Poisoned`] if it is poisoned.
+    pub fn try_read(&self) -> Result<LockReference<'_, T>, LockError> {
+        match self {
+            OwnOrLock::Owned(value) => Ok(LockReference::Borrowed(value)),
+            OwnOrLock::RwLock(lock) => Ok(lock.try_read()?.into()),
+            OwnOrLock::RwLockRef(lock) => Ok(lock.try_read()?.into()),
+            OwnOrLock::RwLockArc(lock) => Ok(lock.try_read()?.into()),
+            OwnOrLock::Mutex(mutex) => Ok(mutex.try_lock()?.into()),
+            OwnOrLock::MutexRef(mutex) => Ok(mutex.try_lock()?.into()),
+            OwnOrLock::MutexArc(mutex) => Ok(mutex.try_lock()?.into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLock(lock) => {
+                Ok(lock.try_read().ok_or(LockError::WouldBlock)?.into())
+            }
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLockRef(lock) => {
+                Ok(lock.try_read().ok_or(LockError::WouldBlock)?.into())
+            }
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLockArc(lock) => {
+                Ok(lock.try_read().ok_or(LockError::WouldBlock)?.into())
+            }
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutex(mutex) => {
+                Ok(mutex.try_lock().ok_or(LockError::WouldBlock)?.into())
+            }
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutexRef(mutex) => {
+                Ok(mutex.try_lock().ok_or(LockError::WouldBlock)?.into())
+            }
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutexArc(mutex) => {
+                Ok(mutex.try_lock().ok_or(LockError::WouldBlock)?.into())
+            }
+        }
+    }
+
+    /// Attempts to lock the inner value for writing without blocking.
+    ///
+    /// Fails with [`LockError::WouldBlock`] if the lock is currently held, or with
+    /// [`LockError::Poisoned`] if it is poisoned. Owned locks never block.
+    pub fn try_write(&mut self) -> Result<LockReferenceMut<'_, T>, LockError> {
+        match self {
+            OwnOrLock::RwLockRef(lock) => Ok(lock.try_write()?.into()),
+            OwnOrLock::RwLockArc(lock) => Ok(lock.try_write()?.into()),
+            OwnOrLock::MutexRef(mutex) => Ok(mutex.try_lock()?.into()),
+            OwnOrLock::MutexArc(mutex) => Ok(mutex.try_lock()?.into()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLockRef(lock) => {
+                Ok(lock.try_write().ok_or(LockError::WouldBlock)?.into())
+            }
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLockArc(lock) => {
+                Ok(lock.try_write().ok_or(LockError::WouldBlock)?.into())
+            }
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutexRef(mutex) => {
+                Ok(mutex.try_lock().ok_or(LockError::WouldBlock)?.into())
+            }
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutexArc(mutex) => {
+                Ok(mutex.try_lock().ok_or(LockError::WouldBlock)?.into())
+            }
+            other => other.write(),
+        }
+    }
+
+    /// Locks the inner value for reading, recovering the guard if the lock is poisoned.
+    ///
+    /// Use this if the protected data is known to stay consistent even if a thread panics
+    /// while holding the lock.
+    pub fn read_recover(&self) -> LockReference<'_, T> {
+        match self {
+            OwnOrLock::RwLock(lock) => lock.read().unwrap_or_else(PoisonError::into_inner).into(),
+            OwnOrLock::RwLockRef(lock) => {
+                lock.read().unwrap_or_else(PoisonError::into_inner).into()
+            }
+            OwnOrLock::RwLockArc(lock) => {
+                lock.read().unwrap_or_else(PoisonError::into_inner).into()
+            }
+            OwnOrLock::Mutex(mutex) => mutex.lock().unwrap_or_else(PoisonError::into_inner).into(),
+            OwnOrLock::MutexRef(mutex) => {
+                mutex.lock().unwrap_or_else(PoisonError::into_inner).into()
+            }
+            OwnOrLock::MutexArc(mutex) => {
+                mutex.lock().unwrap_or_else(PoisonError::into_inner).into()
+            }
+            other => match other.read() {
+                Ok(guard) => guard,
+                Err(_) => unreachable!("only std locks can be poisoned"),
+            },
+        }
+    }
+
+    /// Locks the inner value for writing, recovering the guard if the lock is poisoned.
+    ///
+    /// Use this if the protected data is known to stay consistent even if a thread panics
+    /// while holding the lock.
+    pub fn write_recover(&mut self) -> LockReferenceMut<'_, T> {
+        match self {
+            OwnOrLock::RwLock(lock) => lock
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .into(),
+            OwnOrLock::RwLockRef(lock) => {
+                lock.write().unwrap_or_else(PoisonError::into_inner).into()
+            }
+            OwnOrLock::RwLockArc(lock) => {
+                lock.write().unwrap_or_else(PoisonError::into_inner).into()
+            }
+            OwnOrLock::Mutex(mutex) => mutex
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .into(),
+            OwnOrLock::MutexRef(mutex) => {
+                mutex.lock().unwrap_or_else(PoisonError::into_inner).into()
+            }
+            OwnOrLock::MutexArc(mutex) => {
+                mutex.lock().unwrap_or_else(PoisonError::into_inner).into()
+            }
+            other => match other.write() {
+                Ok(guard) => guard,
+                Err(_) => unreachable!("only std locks can be poisoned"),
+            },
+        }
+    }
+
+    /// Extracts the owned value, recovering it if the lock is poisoned.
+    ///
+    /// Returns the wrapper unchanged if it references a lock or shares it with other
+    /// [`Arc`] owners.
+    pub fn into_inner_recover(self) -> Result<T, Self> {
+        match self {
+            OwnOrLock::Owned(value) => Ok(value),
+            OwnOrLock::RwLock(lock) => {
+                Ok(lock.into_inner().unwrap_or_else(PoisonError::into_inner))
+            }
+            OwnOrLock::RwLockArc(lock) => match Arc::try_unwrap(lock) {
+                Ok(lock) => Ok(lock.into_inner().unwrap_or_else(PoisonError::into_inner)),
+                Err(lock) => Err(OwnOrLock::RwLockArc(lock)),
+            },
+            OwnOrLock::Mutex(mutex) => {
+                Ok(mutex.into_inner().unwrap_or_else(PoisonError::into_inner))
+            }
+            OwnOrLock::MutexArc(mutex) => match Arc::try_unwrap(mutex) {
+                Ok(mutex) => Ok(mutex.into_inner().unwrap_or_else(PoisonError::into_inner)),
+                Err(mutex) => Err(OwnOrLock::MutexArc(mutex)),
+            },
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLock(lock) => Ok(lock.into_inner()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotRwLockArc(lock) => match Arc::try_unwrap(lock) {
+                Ok(lock) => Ok(lock.into_inner()),
+                Err(lock) => Err(OwnOrLock::ParkingLotRwLockArc(lock)),
+            },
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutex(mutex) => Ok(mutex.into_inner()),
+            #[cfg(feature = "parking_lot")]
+            OwnOrLock::ParkingLotMutexArc(mutex) => match Arc::try_unwrap(mutex) {
+                Ok(mutex) => Ok(mutex.into_inner()),
+                Err(mutex) => Err(OwnOrLock::ParkingLotMutexArc(mutex)),
+            },
+            other => Err(other),
+        }
+    }
+}
+
+impl<'a, T> From<RwLock<T>> for OwnOrLock<'a, T> {
+    #[inline]
+    fn from(value: RwLock<T>) -> Self {
+        Self::RwLock(value)
+    }
+}
+
+impl<'a, T> From<&'a RwLock<T>> for OwnOrLock<'a, T> {
+    #[inline]
+    fn from(value: &'a RwLock<T>) -> Self {
+        Self::RwLockRef(value)
+    }
+}
+
+impl<'a, T> From<Arc<RwLock<T>>> for OwnOrLock<'a, T> {
+    #[inline]
+    fn from(value: Arc<RwLock<T>>) -> Self {
+        Self::RwLockArc(value)
+    }
+}
+
+impl<'a, T> From<Mutex<T>> for OwnOrLock<'a, T> {
+    #[inline]
+    fn from(value: Mutex<T>) -> Self {
+        Self::Mutex(value)
+    }
+}
+
+impl<'a, T> From<&'a Mutex<T>> for OwnOrLock<'a, T> {
+    #[inline]
+    fn from(value: &'a Mutex<T>) -> Self {
+        Self::MutexRef(value)
+    }
+}
+
+impl<'a, T> From<Arc<Mutex<T>>> for OwnOrLock<'a, T> {
+    #[inline]
+    fn from(value: Arc<Mutex<T>>) -> Self {
+        Self::MutexArc(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+impl<'a, T> From<parking_lot::RwLock<T>> for OwnOrLock<'a, T> {
+    #[inline]
+    fn from(value: parking_lot::RwLock<T>) -> Self {
+        Self::ParkingLotRwLock(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+impl<'a, T> From<&'a parking_lot::RwLock<T>> for OwnOrLock<'a, T> {
+    #[inline]
+    fn from(value: &'a parking_lot::RwLock<T>) -> Self {
+        Self::ParkingLotRwLockRef(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+impl<'a, T> From<Arc<parking_lot::RwLock<T>>> for OwnOrLock<'a, T> {
+    #[inline]
+    fn from(value: Arc<parking_lot::RwLock<T>>) -> Self {
+        Self::ParkingLotRwLockArc(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+impl<'a, T> From<parking_lot::Mutex<T>> for OwnOrLock<'a, T> {
+    #[inline]
+    fn from(value: parking_lot::Mutex<T>) -> Self {
+        Self::ParkingLotMutex(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+impl<'a, T> From<&'a parking_lot::Mutex<T>> for OwnOrLock<'a, T> {
+    #[inline]
+    fn from(value: &'a parking_lot::Mutex<T>) -> Self {
+        Self::ParkingLotMutexRef(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+impl<'a, T> From<Arc<parking_lot::Mutex<T>>> for OwnOrLock<'a, T> {
+    #[inline]
+    fn from(value: Arc<parking_lot::Mutex<T>>) -> Self {
+        Self::ParkingLotMutexArc(value)
+    }
+}
+
+impl<'a, T> Deref for LockReference<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            LockReference::Borrowed(value) => value,
+            LockReference::RwLock(guard) => guard.deref(),
+            LockReference::Mutex(guard) => guard.deref(),
+            #[cfg(feature = "parking_lot")]
+            LockReference::ParkingLotRwLock(guard) => guard.deref(),
+            #[cfg(feature = "parking_lot")]
+            LockReference::ParkingLotMutex(guard) => guard.deref(),
+        }
+    }
+}
+
+impl<'a, T> Deref for LockReferenceMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            LockReferenceMut::Borrowed(value) => value,
+            LockReferenceMut::RwLock(guard) => guard.deref(),
+            LockReferenceMut::Mutex(guard) => guard.deref(),
+            #[cfg(feature = "parking_lot")]
+            LockReferenceMut::ParkingLotRwLock(guard) => guard.deref(),
+            #[cfg(feature = "parking_lot")]
+            LockReferenceMut::ParkingLotMutex(guard) => guard.deref(),
+        }
+    }
+}
+
+impl<'a, T> DerefMut for LockReferenceMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            LockReferenceMut::Borrowed(value) => value,
+            LockReferenceMut::RwLock(guard) => guard.deref_mut(),
+            LockReferenceMut::Mutex(guard) => guard.deref_mut(),
+            #[cfg(feature = "parking_lot")]
+            LockReferenceMut::ParkingLotRwLock(guard) => guard.deref_mut(),
+            #[cfg(feature = "parking_lot")]
+            LockReferenceMut::ParkingLotMutex(guard) => guard.deref_mut(),
+        }
+    }
+}
+
+impl<'a, T> std::borrow::Borrow<T> for LockReference<'a, T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> std::borrow::Borrow<T> for LockReferenceMut<'a, T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> std::borrow::BorrowMut<T> for LockReferenceMut<'a, T> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+impl<'a, T> AsRef<T> for LockReference<'a, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> AsRef<T> for LockReferenceMut<'a, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> AsMut<T> for LockReferenceMut<'a, T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+impl<'a, T> From<&'a T> for LockReference<'a, T> {
+    #[inline]
+    fn from(value: &'a T) -> Self {
+        LockReference::Borrowed(value)
+    }
+}
+
+impl<'a, T> From<&'a mut T> for LockReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: &'a mut T) -> Self {
+        LockReferenceMut::Borrowed(value)
+    }
+}
+
+impl<'a, T> From<RwLockReadGuard<'a, T>> for LockReference<'a, T> {
+    #[inline]
+    fn from(value: RwLockReadGuard<'a, T>) -> Self {
+        LockReference::RwLock(value)
+    }
+}
+
+impl<'a, T> From<MutexGuard<'a, T>> for LockReference<'a, T> {
+    #[inline]
+    fn from(value: MutexGuard<'a, T>) -> Self {
+        LockReference::Mutex(value)
+    }
+}
+
+impl<'a, T> From<RwLockWriteGuard<'a, T>> for LockReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: RwLockWriteGuard<'a, T>) -> Self {
+        LockReferenceMut::RwLock(value)
+    }
+}
+
+impl<'a, T> From<MutexGuard<'a, T>> for LockReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: MutexGuard<'a, T>) -> Self {
+        LockReferenceMut::Mutex(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+impl<'a, T> From<parking_lot::RwLockReadGuard<'a, T>> for LockReference<'a, T> {
+    #[inline]
+    fn from(value: parking_lot::RwLockReadGuard<'a, T>) -> Self {
+        LockReference::ParkingLotRwLock(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+impl<'a, T> From<parking_lot::MutexGuard<'a, T>> for LockReference<'a, T> {
+    #[inline]
+    fn from(value: parking_lot::MutexGuard<'a, T>) -> Self {
+        LockReference::ParkingLotMutex(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+impl<'a, T> From<parking_lot::RwLockWriteGuard<'a, T>> for LockReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: parking_lot::RwLockWriteGuard<'a, T>) -> Self {
+        LockReferenceMut::ParkingLotRwLock(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
+impl<'a, T> From<parking_lot::MutexGuard<'a, T>> for LockReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: parking_lot::MutexGuard<'a, T>) -> Self {
+        LockReferenceMut::ParkingLotMutex(value)
+    }
+}
+
+impl<'a, T> std::fmt::Debug for OwnOrLock<'a, T>
+where
+    T: std::fmt::Debug,
+{
+    /// Formats the inner value without blocking. Prints `<locked>` if the lock is currently
+    /// held exclusively, or `<poisoned>` if it is poisoned.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.try_read() {
+            Ok(data) => std::fmt::Debug::fmt(data.as_ref(), f),
+            Err(LockError::WouldBlock) => f.write_str("<locked>"),
+            Err(LockError::Poisoned) => f.write_str("<poisoned>"),
+        }
+    }
+}
+
+impl<'a, T> std::fmt::Display for OwnOrLock<'a, T>
+where
+    T: std::fmt::Display,
+{
+    /// Formats the inner value without blocking. Prints `<locked>` if the lock is currently
+    /// held exclusively, or `<poisoned>` if it is poisoned.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.try_read() {
+            Ok(data) => std::fmt::Display::fmt(data.as_ref(), f),
+            Err(LockError::WouldBlock) => f.write_str("<locked>"),
+            Err(LockError::Poisoned) => f.write_str("<poisoned>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_write_owned_type() {
+        let mut value = OwnOrLock::own(42);
+        assert_eq!(value.read().expect("failed to read").as_ref(), &42);
+        *value.write().expect("failed to write") = 17;
+        assert_eq!(value.read().expect("failed to read").as_ref(), &17);
+    }
+
+    #[test]
+    fn read_write_rwlock() {
+        let mut value = OwnOrLock::from(RwLock::new(42));
+        *value.write().expect("failed to write") += 1;
+        assert_eq!(value.read().expect("failed to read").as_ref(), &43);
+    }
+
+    #[test]
+    fn read_write_mutex_ref() {
+        let mutex = Mutex::new(42);
+        let mut value = OwnOrLock::from(&mutex);
+        *value.write().expect("failed to write") += 1;
+        assert_eq!(value.read().expect("failed to read").as_ref(), &43);
+        assert_eq!(*mutex.lock().unwrap(), 43);
+    }
+
+    #[test]
+    fn share_across_threads() {
+        let lock = RwLock::new(0);
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    let mut value = OwnOrLock::from(&lock);
+                    *value.write().expect("failed to write") += 1;
+                });
+            }
+        });
+        assert_eq!(*lock.read().unwrap(), 4);
+    }
+
+    #[test]
+    fn arc_keeps_value_alive_across_threads() {
+        let mutex = Arc::new(Mutex::new(0));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let mut value = OwnOrLock::from(mutex.clone());
+                std::thread::spawn(move || *value.write().expect("failed to write") += 1)
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*mutex.lock().unwrap(), 4);
+    }
+
+    #[test]
+    fn poisoned_lock_is_reported() {
+        let mutex = Mutex::new(42);
+        let _ = std::thread::scope(|s| {
+            s.spawn(|| {
+                let _guard = mutex.lock().unwrap();
+                panic!("poisoning the lock");
+            })
+            .join()
+        });
+
+        let mut value = OwnOrLock::from(&mutex);
+        assert!(matches!(value.read(), Err(LockError::Poisoned)));
+        assert!(matches!(value.write(), Err(LockError::Poisoned)));
+        assert_eq!(format!("{:?}", value), "<poisoned>");
+    }
+
+    #[test]
+    fn try_lock_would_block() {
+        let lock = RwLock::new(42);
+        let mut value = OwnOrLock::from(&lock);
+        {
+            let _guard = lock.read().unwrap();
+            assert!(value.try_read().is_ok());
+            assert!(matches!(value.try_write(), Err(LockError::WouldBlock)));
+        }
+        *value.try_write().expect("failed to write") += 1;
+
+        let _guard = lock.write().unwrap();
+        assert!(matches!(value.try_read(), Err(LockError::WouldBlock)));
+    }
+
+    #[test]
+    fn format_while_locked() {
+        let mutex = Mutex::new(42);
+        let value = OwnOrLock::from(&mutex);
+        {
+            let _guard = value.read().expect("failed to read");
+            assert_eq!(format!("{:?}", value), "<locked>");
+        }
+
+        let lock = RwLock::new(42);
+        let mut writer = OwnOrLock::from(&lock);
+        let value = OwnOrLock::from(&lock);
+        let _guard = writer.write().expect("failed to write");
+        assert_eq!(format!("{}", value), "<locked>");
+    }
+
+    #[test]
+    fn poisoned_lock_is_recovered() {
+        let lock = RwLock::new(42);
+        let _ = std::thread::scope(|s| {
+            s.spawn(|| {
+                let _guard = lock.write().unwrap();
+                panic!("poisoning the lock");
+            })
+            .join()
+        });
+
+        let mut value = OwnOrLock::from(&lock);
+        assert!(matches!(value.try_read(), Err(LockError::Poisoned)));
+        *value.write_recover() += 1;
+        assert_eq!(*value.read_recover(), 43);
+        assert!(value.into_inner_recover().is_err());
+        assert_eq!(OwnOrLock::from(lock).into_inner_recover().ok(), Some(43));
+    }
+
+    #[test]
+    fn into_inner_recover_shared_arc() {
+        let mutex = Arc::new(Mutex::new(42));
+        let value = OwnOrLock::from(mutex.clone());
+        let Err(value) = value.into_inner_recover() else {
+            panic!("the mutex is shared");
+        };
+        drop(mutex);
+        assert_eq!(value.into_inner_recover().ok(), Some(42));
+    }
+
+    #[test]
+    #[cfg(feature = "parking_lot")]
+    fn read_write_parking_lot_rwlock_ref() {
+        let lock = parking_lot::RwLock::new(42);
+        let mut value = OwnOrLock::from(&lock);
+        *value.write().expect("failed to write") += 1;
+        assert_eq!(value.read().expect("failed to read").as_ref(), &43);
+        assert_eq!(*lock.read(), 43);
+    }
+
+    #[test]
+    #[cfg(feature = "parking_lot")]
+    fn read_write_parking_lot_mutex() {
+        let mut value = OwnOrLock::from(parking_lot::Mutex::new(42));
+        *value.write().expect("failed to write") += 1;
+        assert!(matches!(
+            value.read().expect("failed to read"),
+            LockReference::ParkingLotMutex(_)
+        ));
+        assert_eq!(format!("{}", value), "43");
+    }
+
+    #[test]
+    #[cfg(feature = "parking_lot")]
+    fn parking_lot_mutex_is_not_poisoned() {
+        let mutex = Arc::new(parking_lot::Mutex::new(42));
+        let shared = mutex.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = shared.lock();
+            panic!("attempting to poison the lock");
+        })
+        .join();
+
+        let mut value = OwnOrLock::from(mutex);
+        *value.write().expect("failed to write") += 1;
+        assert_eq!(value.read().expect("failed to read").as_ref(), &43);
+    }
+}
@@ -0,0 +1,197 @@
+//! Access to pinned values.
+
+use crate::{OwnOrBorrow, Reference, ReferenceMut};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::future::Future;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// A shared borrow of a pinned value, as returned by [`OwnOrBorrow::borrow_pin`].
+pub struct PinReference<'a, P> {
+    inner: Reference<'a, Pin<P>>,
+}
+
+/// A mutable borrow of a pinned value, as returned by [`OwnOrBorrow::borrow_pin_mut`].
+///
+/// Unlike [`ReferenceMut`], this only hands out `Pin<&mut T>`, so pinning invariants hold
+/// regardless of where the value is stored. If the value is a [`Future`], the guard can be
+/// awaited or polled directly.
+pub struct PinReferenceMut<'a, P> {
+    inner: ReferenceMut<'a, Pin<P>>,
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T> OwnOrBorrow<'a, Pin<Box<T>>> {
+    /// Initializes a new instance that owns the value pinned on the heap.
+    ///
+    /// ```
+    /// use own_or_borrow::OwnOrBorrow;
+    /// use core::future::Future;
+    /// use core::task::{Context, Poll, Waker};
+    ///
+    /// let mut value = OwnOrBorrow::pin(async { 42 });
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// assert_eq!(value.borrow_pin_mut().as_mut().poll(&mut cx), Poll::Ready(42));
+    /// ```
+    pub fn pin(value: T) -> Self {
+        Self::Owned(Box::pin(value))
+    }
+}
+
+impl<'a, P> OwnOrBorrow<'a, Pin<P>>
+where
+    P: Deref,
+{
+    /// Borrows the pinned value.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn borrow_pin(&self) -> PinReference<'_, P> {
+        PinReference {
+            inner: self.borrow(),
+        }
+    }
+
+    /// Borrows the pinned value mutably.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed, or if it is a plain shared reference
+    /// ([`OwnOrBorrow::Borrowed`]).
+    #[track_caller]
+    pub fn borrow_pin_mut(&mut self) -> PinReferenceMut<'_, P>
+    where
+        P: DerefMut,
+    {
+        PinReferenceMut {
+            inner: self.borrow_mut(),
+        }
+    }
+}
+
+impl<'a, P> PinReference<'a, P>
+where
+    P: Deref,
+{
+    /// Returns the pinned shared reference.
+    #[inline]
+    pub fn as_ref(&self) -> Pin<&P::Target> {
+        self.inner.deref().as_ref()
+    }
+}
+
+impl<'a, P> PinReferenceMut<'a, P>
+where
+    P: DerefMut,
+{
+    /// Returns the pinned shared reference.
+    #[inline]
+    pub fn as_ref(&self) -> Pin<&P::Target> {
+        self.inner.deref().as_ref()
+    }
+
+    /// Returns the pinned mutable reference.
+    #[inline]
+    pub fn as_mut(&mut self) -> Pin<&mut P::Target> {
+        self.inner.deref_mut().as_mut()
+    }
+}
+
+impl<'a, P> Deref for PinReference<'a, P>
+where
+    P: Deref,
+{
+    type Target = P::Target;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.inner.deref()
+    }
+}
+
+impl<'a, P> Deref for PinReferenceMut<'a, P>
+where
+    P: Deref,
+{
+    type Target = P::Target;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.inner.deref()
+    }
+}
+
+/// Mutable access without pinning is only available for [`Unpin`] values, just like for
+/// [`Pin`] itself.
+impl<'a, P> DerefMut for PinReferenceMut<'a, P>
+where
+    P: DerefMut,
+    P::Target: Unpin,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.inner.deref_mut()
+    }
+}
+
+impl<'a, P> Future for PinReferenceMut<'a, P>
+where
+    P: DerefMut,
+    P::Target: Future,
+{
+    type Output = <P::Target as Future>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut().as_mut().poll(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+    use core::task::Waker;
+
+    #[test]
+    fn poll_future_through_refcell() {
+        let mut future = core::pin::pin!(async { 42 });
+        let cell = RefCell::new(future.as_mut());
+        let mut value = OwnOrBorrow::from(&cell);
+        let mut cx = Context::from_waker(Waker::noop());
+        assert_eq!(
+            Pin::new(&mut value.borrow_pin_mut()).poll(&mut cx),
+            Poll::Ready(42)
+        );
+        assert!(cell.try_borrow_mut().is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn borrow_pin_of_unpinnable_value() {
+        use core::marker::PhantomPinned;
+
+        struct Pinned {
+            value: u32,
+            _pinned: PhantomPinned,
+        }
+
+        let value = OwnOrBorrow::pin(Pinned {
+            value: 42,
+            _pinned: PhantomPinned,
+        });
+        assert_eq!(value.borrow_pin().as_ref().value, 42);
+        assert_eq!(value.borrow_pin().value, 42);
+    }
+
+    #[test]
+    fn deref_mut_for_unpin_values() {
+        let mut data = 1;
+        let mut value = OwnOrBorrow::own(Pin::new(&mut data));
+        *value.borrow_pin_mut() += 1;
+        *value.borrow_pin_mut().as_mut() += 1;
+        assert_eq!(data, 3);
+    }
+}
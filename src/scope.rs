@@ -0,0 +1,300 @@
+//! Scoped borrows with descriptive conflict messages.
+
+use crate::audit::{self, Source, Wrapper};
+use crate::{BorrowFault, OwnOrBorrow, Reference, ReferenceMut};
+use core::cell::Cell;
+use core::fmt::{Display, Formatter};
+use core::ops::{Deref, DerefMut};
+use core::panic::Location;
+
+/// A borrow scope over an [`OwnOrBorrow`], as returned by [`OwnOrBorrow::scoped`].
+///
+/// Conflicting borrows taken through the scope panic with a message naming the location where
+/// the scope was opened, instead of the bare `already borrowed` message of the
+/// [`RefCell`](core::cell::RefCell). With the `borrow-diagnostics` feature, the message also
+/// names the location of the last borrow taken through the scope.
+///
+/// The guards handed out by the scope borrow it and cannot outlive it. The scope counts them
+/// and panics on drop if one was leaked, e.g. through [`mem::forget`](core::mem::forget),
+/// which would leave the value borrowed for good. Borrows taken directly on the
+/// [`RefCell`](core::cell::RefCell) are not tracked.
+///
+/// ```
+/// use own_or_borrow::OwnOrBorrow;
+/// use core::cell::RefCell;
+///
+/// let cell = RefCell::new(vec![1, 2, 3]);
+/// let value = OwnOrBorrow::from(&cell);
+///
+/// let scope = value.scoped();
+/// scope.borrow_mut().push(4);
+/// assert_eq!(scope.borrow().len(), 4);
+/// ```
+pub struct ScopedBorrow<'s, 'a, T> {
+    value: &'s OwnOrBorrow<'a, T>,
+    live: Cell<usize>,
+    opened_at: &'static Location<'static>,
+    #[cfg(feature = "borrow-diagnostics")]
+    last_borrow: Cell<Option<(&'static Location<'static>, bool)>>,
+}
+
+/// A shared borrow taken through a [`ScopedBorrow`].
+pub struct ScopedReference<'g, T> {
+    guard: Reference<'g, T>,
+    live: &'g Cell<usize>,
+}
+
+/// A mutable borrow taken through a [`ScopedBorrow`].
+pub struct ScopedReferenceMut<'g, T> {
+    guard: ReferenceMut<'g, T>,
+    live: &'g Cell<usize>,
+}
+
+impl<'a, T> OwnOrBorrow<'a, T> {
+    /// Opens a borrow scope with descriptive conflict messages, see [`ScopedBorrow`].
+    #[track_caller]
+    pub fn scoped<'s>(&'s self) -> ScopedBorrow<'s, 'a, T> {
+        ScopedBorrow {
+            value: self,
+            live: Cell::new(0),
+            opened_at: Location::caller(),
+            #[cfg(feature = "borrow-diagnostics")]
+            last_borrow: Cell::new(None),
+        }
+    }
+}
+
+impl<'s, 'a, T> ScopedBorrow<'s, 'a, T> {
+    /// Returns the location where the scope was opened.
+    pub fn opened_at(&self) -> &'static Location<'static> {
+        self.opened_at
+    }
+
+    /// Attempts to borrow the inner value.
+    #[track_caller]
+    pub fn try_borrow(&self) -> Result<ScopedReference<'_, T>, BorrowFault> {
+        let guard = self.value.try_borrow()?;
+        self.record(false);
+        Ok(ScopedReference {
+            guard,
+            live: &self.live,
+        })
+    }
+
+    /// Attempts to borrow the inner value mutably.
+    ///
+    /// As the scope only holds a shared reference to the wrapper, this requires a
+    /// [`RefCell`](core::cell::RefCell) backed value and fails with [`BorrowFault::Shared`]
+    /// otherwise.
+    #[track_caller]
+    pub fn try_borrow_mut(&self) -> Result<ScopedReferenceMut<'_, T>, BorrowFault> {
+        let ref_cell = self.value.as_refcell().ok_or(BorrowFault::Shared)?;
+        let guard = audit::try_borrow_mut(Wrapper::ScopedBorrow, ref_cell, source(self.value))?;
+        self.record(true);
+        Ok(ScopedReferenceMut {
+            guard: ReferenceMut::RefCellRef(guard, ref_cell),
+            live: &self.live,
+        })
+    }
+
+    /// Borrows the inner value.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed. The message names the location
+    /// where the scope was opened.
+    #[track_caller]
+    pub fn borrow(&self) -> ScopedReference<'_, T> {
+        match self.try_borrow() {
+            Ok(value) => value,
+            Err(error) => panic!("{}", self.conflict(error)),
+        }
+    }
+
+    /// Borrows the inner value mutably, see [`try_borrow_mut`](Self::try_borrow_mut).
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed or is not [`RefCell`](core::cell::RefCell)
+    /// backed. The message names the location where the scope was opened.
+    #[track_caller]
+    pub fn borrow_mut(&self) -> ScopedReferenceMut<'_, T> {
+        match self.try_borrow_mut() {
+            Ok(value) => value,
+            Err(error) => panic!("{}", self.conflict(error)),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn record(&self, exclusive: bool) {
+        self.live.set(self.live.get() + 1);
+
+        #[cfg(feature = "borrow-diagnostics")]
+        self.last_borrow.set(Some((Location::caller(), exclusive)));
+
+        #[cfg(not(feature = "borrow-diagnostics"))]
+        let _ = exclusive;
+    }
+
+    fn conflict(&self, error: BorrowFault) -> Diagnostic<'_, 's, 'a, T> {
+        Diagnostic {
+            scope: self,
+            error: Some(error),
+        }
+    }
+}
+
+impl<'s, 'a, T> Drop for ScopedBorrow<'s, 'a, T> {
+    /// ## Panics
+    /// Panics if a guard handed out by the scope was leaked. The check is skipped while the
+    /// thread is panicking, and without the `std` feature whenever panics unwind, since the
+    /// panic state cannot be queried there.
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        let may_be_unwinding = std::thread::panicking();
+        #[cfg(not(feature = "std"))]
+        let may_be_unwinding = cfg!(panic = "unwind");
+
+        if !may_be_unwinding && self.live.get() != 0 {
+            panic!(
+                "{}",
+                Diagnostic {
+                    scope: self,
+                    error: None,
+                }
+            );
+        }
+    }
+}
+
+impl<'g, T> Drop for ScopedReference<'g, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.live.set(self.live.get() - 1);
+    }
+}
+
+impl<'g, T> Drop for ScopedReferenceMut<'g, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.live.set(self.live.get() - 1);
+    }
+}
+
+impl<'g, T> Deref for ScopedReference<'g, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<'g, T> Deref for ScopedReferenceMut<'g, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<'g, T> DerefMut for ScopedReferenceMut<'g, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+/// A borrow conflict or leaked borrow of a [`ScopedBorrow`], formatted for panic messages.
+struct Diagnostic<'d, 's, 'a, T> {
+    scope: &'d ScopedBorrow<'s, 'a, T>,
+    error: Option<BorrowFault>,
+}
+
+impl<'d, 's, 'a, T> Display for Diagnostic<'d, 's, 'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let source = source(self.scope.value).describe();
+        match &self.error {
+            Some(error) => write!(f, "{} ({})", error, source)?,
+            None => write!(f, "borrow of the {} escaped its scope", source)?,
+        }
+        write!(f, " in the scope opened at {}", self.scope.opened_at)?;
+
+        #[cfg(feature = "borrow-diagnostics")]
+        if let Some((location, exclusive)) = self.scope.last_borrow.get() {
+            let kind = if exclusive { "mutable" } else { "shared" };
+            write!(
+                f,
+                "; last {} borrow in the scope taken at {}",
+                kind, location
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn source<T>(value: &OwnOrBorrow<'_, T>) -> Source {
+    match value {
+        OwnOrBorrow::Owned(_) => Source::Owned,
+        OwnOrBorrow::RefCell(_) => Source::RefCell,
+        OwnOrBorrow::RefCellRef(_) => Source::RefCellRef,
+        OwnOrBorrow::Borrowed(_) => Source::Borrowed,
+        OwnOrBorrow::BorrowedMut(_) => Source::BorrowedMut,
+        #[cfg(feature = "alloc")]
+        OwnOrBorrow::RefCellRc(_) => Source::RefCellRc,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    #[test]
+    fn borrows_through_scope() {
+        let cell = RefCell::new(1);
+        let value = OwnOrBorrow::from(&cell);
+        let scope = value.scoped();
+        *scope.borrow_mut() += 1;
+        assert_eq!(*scope.borrow(), 2);
+        assert_eq!(scope.opened_at().file(), file!());
+    }
+
+    #[test]
+    fn owned_value_cannot_be_borrowed_mutably() {
+        let value = OwnOrBorrow::own(1);
+        let scope = value.scoped();
+        assert!(matches!(scope.try_borrow_mut(), Err(BorrowFault::Shared)));
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed (referenced RefCell) in the scope opened at")]
+    fn conflict_names_scope() {
+        let cell = RefCell::new(1);
+        let value = OwnOrBorrow::from(&cell);
+        let scope = value.scoped();
+        let _guard = scope.borrow();
+        let _ = scope.borrow_mut();
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[should_panic(expected = "escaped its scope")]
+    fn leaked_borrow_is_reported() {
+        let cell = RefCell::new(1);
+        let value = OwnOrBorrow::from(&cell);
+        let scope = value.scoped();
+        core::mem::forget(scope.borrow());
+    }
+
+    #[test]
+    fn untracked_borrows_are_ignored() {
+        let cell = RefCell::new(1);
+        let value = OwnOrBorrow::from(&cell);
+        let scope = value.scoped();
+        let guard = cell.borrow();
+        drop(scope);
+        drop(guard);
+    }
+}
@@ -0,0 +1,91 @@
+//! `zeroize` support.
+
+use crate::OwnOrBorrow;
+use zeroize::Zeroize;
+
+/// Zeroizes the owned [`OwnOrBorrow::Owned`] and [`OwnOrBorrow::RefCell`] variants.
+///
+/// Borrowed variants, including a shared `OwnOrBorrow::RefCellRc`, are left untouched as
+/// their data belongs to someone else; wiping it is the caller's responsibility.
+///
+/// `OwnOrBorrow` does not implement `ZeroizeOnDrop`: that would require a `Drop`
+/// implementation, which forbids moving the value out of the wrapper by destructuring, as
+/// [`try_unwrap`](OwnOrBorrow::try_unwrap) and [`into_owned`](OwnOrBorrow::into_owned) do.
+/// To wipe owned secrets on drop, wrap them in [`Zeroizing`](zeroize::Zeroizing) instead:
+///
+/// ```
+/// use own_or_borrow::OwnOrBorrow;
+/// use zeroize::Zeroizing;
+///
+/// let key: OwnOrBorrow<'_, Zeroizing<[u8; 4]>> = OwnOrBorrow::own(Zeroizing::new([1, 2, 3, 4]));
+/// assert_eq!(key.borrow()[0], 1);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<'a, T> Zeroize for OwnOrBorrow<'a, T>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        match self {
+            OwnOrBorrow::Owned(value) => value.zeroize(),
+            OwnOrBorrow::RefCell(ref_cell) => ref_cell.get_mut().zeroize(),
+            OwnOrBorrow::RefCellRef(_) | OwnOrBorrow::Borrowed(_) | OwnOrBorrow::BorrowedMut(_) => {
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrow::RefCellRc(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::{Cell, RefCell};
+    use zeroize::Zeroizing;
+
+    struct Key<'w> {
+        bytes: [u8; 3],
+        wiped: &'w Cell<bool>,
+    }
+
+    impl Zeroize for Key<'_> {
+        fn zeroize(&mut self) {
+            self.bytes.zeroize();
+            self.wiped.set(true);
+        }
+    }
+
+    #[test]
+    fn zeroize_owned_variants() {
+        let mut value = OwnOrBorrow::own([1u8, 2, 3]);
+        value.zeroize();
+        assert_eq!(*value.borrow(), [0, 0, 0]);
+
+        let mut value = OwnOrBorrow::new_refcell([1u8, 2, 3]);
+        value.zeroize();
+        assert_eq!(*value.borrow(), [0, 0, 0]);
+    }
+
+    #[test]
+    fn borrowed_variants_are_untouched() {
+        let cell = RefCell::new([1u8, 2, 3]);
+        OwnOrBorrow::from(&cell).zeroize();
+        assert_eq!(*cell.borrow(), [1, 2, 3]);
+
+        let mut data = [1u8, 2, 3];
+        OwnOrBorrow::from(&mut data).zeroize();
+        assert_eq!(data, [1, 2, 3]);
+    }
+
+    #[test]
+    fn zeroizing_wipes_owned_values_on_drop() {
+        let wiped = Cell::new(false);
+        let key = OwnOrBorrow::own(Zeroizing::new(Key {
+            bytes: [1, 2, 3],
+            wiped: &wiped,
+        }));
+        assert_eq!(key.borrow().bytes, [1, 2, 3]);
+        drop(key);
+        assert!(wiped.get());
+    }
+}
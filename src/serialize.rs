@@ -0,0 +1,65 @@
+//! `serde` support.
+
+use crate::OwnOrBorrow;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Error, Serialize, Serializer};
+
+/// Serializes the inner value transparently, regardless of the variant.
+///
+/// Serialization fails if the value is currently mutably borrowed.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'a, T> Serialize for OwnOrBorrow<'a, T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.try_borrow() {
+            Ok(value) => T::serialize(&value, serializer),
+            Err(error) => Err(S::Error::custom(error)),
+        }
+    }
+}
+
+/// Deserializes the inner value into the [`OwnOrBorrow::Owned`] variant.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, 'a, T> Deserialize<'de> for OwnOrBorrow<'a, T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(OwnOrBorrow::Owned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    #[test]
+    fn serialize_transparently() {
+        let cell = RefCell::new([1, 2, 3]);
+        let value = OwnOrBorrow::from(&cell);
+        assert_eq!(serde_json::to_string(&value).unwrap(), "[1,2,3]");
+    }
+
+    #[test]
+    fn serialize_fails_while_mutably_borrowed() {
+        let cell = RefCell::new(42);
+        let value = OwnOrBorrow::from(&cell);
+        let _guard = cell.borrow_mut();
+        assert!(serde_json::to_string(&value).is_err());
+    }
+
+    #[test]
+    fn deserialize_into_owned() {
+        let value: OwnOrBorrow<'_, u32> = serde_json::from_str("42").unwrap();
+        assert!(matches!(value, OwnOrBorrow::Owned(42)));
+    }
+}
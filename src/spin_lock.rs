@@ -0,0 +1,410 @@
+//! `no_std` thread-safe counterpart to [`OwnOrBorrow`](crate::OwnOrBorrow) built on
+//! `spin`.
+
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+use core::ops::{Deref, DerefMut};
+use spin::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A type that provides either an owned value or a [`spin::RwLock`] or [`spin::Mutex`]
+/// protected value.
+///
+/// This mirrors `OwnOrLock` for `no_std` targets: the locks spin instead of parking the
+/// thread, so the wrapper can share state between cores without an operating system. It is
+/// [`Sync`] whenever `T` is [`Send`] and [`Sync`].
+///
+/// Borrowing returns the dedicated [`SpinReference`] and [`SpinReferenceMut`] guards rather
+/// than [`Reference`](crate::Reference) and [`ReferenceMut`](crate::ReferenceMut): `spin`
+/// guards cannot be projected to a component of the value, so the
+/// [`Reference::map`](crate::Reference::map) family could not support them without unsafe
+/// code. `OwnOrLock` uses separate guards for the same reason.
+///
+/// ```
+/// use own_or_borrow::OwnOrSpin;
+/// use spin::Mutex;
+///
+/// static COUNTER: Mutex<u32> = Mutex::new(0);
+///
+/// fn on_core(counter: &mut OwnOrSpin<'_, u32>) {
+///     *counter.write() += 1;
+/// }
+///
+/// on_core(&mut OwnOrSpin::from(&COUNTER));
+/// on_core(&mut OwnOrSpin::own(0));
+/// assert_eq!(*COUNTER.lock(), 1);
+/// ```
+pub enum OwnOrSpin<'a, T> {
+    /// An owned value.
+    Owned(T),
+    /// An owned [`spin::RwLock`].
+    RwLock(RwLock<T>),
+    /// A reference to a [`spin::RwLock`].
+    RwLockRef(&'a RwLock<T>),
+    /// A shared, reference-counted [`spin::RwLock`]. The wrapper keeps the value alive.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    RwLockArc(Arc<RwLock<T>>),
+    /// An owned [`spin::Mutex`].
+    Mutex(Mutex<T>),
+    /// A reference to a [`spin::Mutex`].
+    MutexRef(&'a Mutex<T>),
+    /// A shared, reference-counted [`spin::Mutex`]. The wrapper keeps the value alive.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    MutexArc(Arc<Mutex<T>>),
+}
+
+/// A reference to owned or spin lock protected data.
+pub enum SpinReference<'a, T> {
+    /// A reference to an owned value.
+    Borrowed(&'a T),
+    /// A [`spin::RwLock`] read guard.
+    RwLock(RwLockReadGuard<'a, T>),
+    /// A [`spin::Mutex`] guard.
+    Mutex(MutexGuard<'a, T>),
+}
+
+/// A mutable reference to owned or spin lock protected data.
+pub enum SpinReferenceMut<'a, T> {
+    /// A mutable reference to an owned value.
+    Borrowed(&'a mut T),
+    /// A [`spin::RwLock`] write guard.
+    RwLock(RwLockWriteGuard<'a, T>),
+    /// A [`spin::Mutex`] guard.
+    Mutex(MutexGuard<'a, T>),
+}
+
+impl<'a, T> OwnOrSpin<'a, T> {
+    /// Initializes a new instance that owns data.
+    pub const fn own(value: T) -> Self {
+        Self::Owned(value)
+    }
+
+    /// Initializes a new instance that owns data wrapped in a [`spin::RwLock`].
+    pub const fn new_rwlock(value: T) -> Self {
+        Self::RwLock(RwLock::new(value))
+    }
+
+    /// Initializes a new instance that owns data wrapped in a [`spin::Mutex`].
+    pub const fn new_mutex(value: T) -> Self {
+        Self::Mutex(Mutex::new(value))
+    }
+
+    /// Locks the inner value for reading.
+    ///
+    /// This spins until the lock can be acquired. A [`spin::Mutex`] is locked exclusively.
+    pub fn read(&self) -> SpinReference<'_, T> {
+        match self {
+            OwnOrSpin::Owned(value) => SpinReference::Borrowed(value),
+            OwnOrSpin::RwLock(lock) => lock.read().into(),
+            OwnOrSpin::RwLockRef(lock) => lock.read().into(),
+            #[cfg(feature = "alloc")]
+            OwnOrSpin::RwLockArc(lock) => lock.read().into(),
+            OwnOrSpin::Mutex(mutex) => mutex.lock().into(),
+            OwnOrSpin::MutexRef(mutex) => mutex.lock().into(),
+            #[cfg(feature = "alloc")]
+            OwnOrSpin::MutexArc(mutex) => mutex.lock().into(),
+        }
+    }
+
+    /// Locks the inner value for writing.
+    ///
+    /// This spins until the lock can be acquired. Owned locks are accessed without locking
+    /// since `&mut self` already guarantees exclusive access.
+    pub fn write(&mut self) -> SpinReferenceMut<'_, T> {
+        match self {
+            OwnOrSpin::Owned(value) => SpinReferenceMut::Borrowed(value),
+            OwnOrSpin::RwLock(lock) => lock.get_mut().into(),
+            OwnOrSpin::RwLockRef(lock) => lock.write().into(),
+            #[cfg(feature = "alloc")]
+            OwnOrSpin::RwLockArc(lock) => lock.write().into(),
+            OwnOrSpin::Mutex(mutex) => mutex.get_mut().into(),
+            OwnOrSpin::MutexRef(mutex) => mutex.lock().into(),
+            #[cfg(feature = "alloc")]
+            OwnOrSpin::MutexArc(mutex) => mutex.lock().into(),
+        }
+    }
+
+    /// Attempts to lock the inner value for reading without spinning.
+    ///
+    /// Returns `None` if the lock is currently held exclusively.
+    pub fn try_read(&self) -> Option<SpinReference<'_, T>> {
+        match self {
+            OwnOrSpin::Owned(value) => Some(SpinReference::Borrowed(value)),
+            OwnOrSpin::RwLock(lock) => lock.try_read().map(Into::into),
+            OwnOrSpin::RwLockRef(lock) => lock.try_read().map(Into::into),
+            #[cfg(feature = "alloc")]
+            OwnOrSpin::RwLockArc(lock) => lock.try_read().map(Into::into),
+            OwnOrSpin::Mutex(mutex) => mutex.try_lock().map(Into::into),
+            OwnOrSpin::MutexRef(mutex) => mutex.try_lock().map(Into::into),
+            #[cfg(feature = "alloc")]
+            OwnOrSpin::MutexArc(mutex) => mutex.try_lock().map(Into::into),
+        }
+    }
+
+    /// Attempts to lock the inner value for writing without spinning.
+    ///
+    /// Returns `None` if the lock is currently held. Owned locks always succeed.
+    pub fn try_write(&mut self) -> Option<SpinReferenceMut<'_, T>> {
+        match self {
+            OwnOrSpin::RwLockRef(lock) => lock.try_write().map(Into::into),
+            #[cfg(feature = "alloc")]
+            OwnOrSpin::RwLockArc(lock) => lock.try_write().map(Into::into),
+            OwnOrSpin::MutexRef(mutex) => mutex.try_lock().map(Into::into),
+            #[cfg(feature = "alloc")]
+            OwnOrSpin::MutexArc(mutex) => mutex.try_lock().map(Into::into),
+            other => Some(other.write()),
+        }
+    }
+}
+
+impl<'a, T> From<RwLock<T>> for OwnOrSpin<'a, T> {
+    #[inline]
+    fn from(value: RwLock<T>) -> Self {
+        Self::RwLock(value)
+    }
+}
+
+impl<'a, T> From<&'a RwLock<T>> for OwnOrSpin<'a, T> {
+    #[inline]
+    fn from(value: &'a RwLock<T>) -> Self {
+        Self::RwLockRef(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T> From<Arc<RwLock<T>>> for OwnOrSpin<'a, T> {
+    #[inline]
+    fn from(value: Arc<RwLock<T>>) -> Self {
+        Self::RwLockArc(value)
+    }
+}
+
+impl<'a, T> From<Mutex<T>> for OwnOrSpin<'a, T> {
+    #[inline]
+    fn from(value: Mutex<T>) -> Self {
+        Self::Mutex(value)
+    }
+}
+
+impl<'a, T> From<&'a Mutex<T>> for OwnOrSpin<'a, T> {
+    #[inline]
+    fn from(value: &'a Mutex<T>) -> Self {
+        Self::MutexRef(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T> From<Arc<Mutex<T>>> for OwnOrSpin<'a, T> {
+    #[inline]
+    fn from(value: Arc<Mutex<T>>) -> Self {
+        Self::MutexArc(value)
+    }
+}
+
+impl<'a, T> Deref for SpinReference<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            SpinReference::Borrowed(value) => value,
+            SpinReference::RwLock(guard) => guard.deref(),
+            SpinReference::Mutex(guard) => guard.deref(),
+        }
+    }
+}
+
+impl<'a, T> Deref for SpinReferenceMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            SpinReferenceMut::Borrowed(value) => value,
+            SpinReferenceMut::RwLock(guard) => guard.deref(),
+            SpinReferenceMut::Mutex(guard) => guard.deref(),
+        }
+    }
+}
+
+impl<'a, T> DerefMut for SpinReferenceMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            SpinReferenceMut::Borrowed(value) => value,
+            SpinReferenceMut::RwLock(guard) => guard.deref_mut(),
+            SpinReferenceMut::Mutex(guard) => guard.deref_mut(),
+        }
+    }
+}
+
+impl<'a, T> core::borrow::Borrow<T> for SpinReference<'a, T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> core::borrow::Borrow<T> for SpinReferenceMut<'a, T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> core::borrow::BorrowMut<T> for SpinReferenceMut<'a, T> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+impl<'a, T> AsRef<T> for SpinReference<'a, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> AsRef<T> for SpinReferenceMut<'a, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> AsMut<T> for SpinReferenceMut<'a, T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+impl<'a, T> From<&'a T> for SpinReference<'a, T> {
+    #[inline]
+    fn from(value: &'a T) -> Self {
+        SpinReference::Borrowed(value)
+    }
+}
+
+impl<'a, T> From<&'a mut T> for SpinReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: &'a mut T) -> Self {
+        SpinReferenceMut::Borrowed(value)
+    }
+}
+
+impl<'a, T> From<RwLockReadGuard<'a, T>> for SpinReference<'a, T> {
+    #[inline]
+    fn from(value: RwLockReadGuard<'a, T>) -> Self {
+        SpinReference::RwLock(value)
+    }
+}
+
+impl<'a, T> From<MutexGuard<'a, T>> for SpinReference<'a, T> {
+    #[inline]
+    fn from(value: MutexGuard<'a, T>) -> Self {
+        SpinReference::Mutex(value)
+    }
+}
+
+impl<'a, T> From<RwLockWriteGuard<'a, T>> for SpinReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: RwLockWriteGuard<'a, T>) -> Self {
+        SpinReferenceMut::RwLock(value)
+    }
+}
+
+impl<'a, T> From<MutexGuard<'a, T>> for SpinReferenceMut<'a, T> {
+    #[inline]
+    fn from(value: MutexGuard<'a, T>) -> Self {
+        SpinReferenceMut::Mutex(value)
+    }
+}
+
+impl<'a, T> core::fmt::Debug for OwnOrSpin<'a, T>
+where
+    T: core::fmt::Debug,
+{
+    /// Formats the inner value without spinning, or prints `<locked>` if the lock is
+    /// currently held exclusively.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.try_read() {
+            Some(data) => core::fmt::Debug::fmt(data.as_ref(), f),
+            None => f.write_str("<locked>"),
+        }
+    }
+}
+
+impl<'a, T> core::fmt::Display for OwnOrSpin<'a, T>
+where
+    T: core::fmt::Display,
+{
+    /// Formats the inner value without spinning, or prints `<locked>` if the lock is
+    /// currently held exclusively.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.try_read() {
+            Some(data) => core::fmt::Display::fmt(data.as_ref(), f),
+            None => f.write_str("<locked>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_write_owned_type() {
+        let mut value = OwnOrSpin::own(42);
+        *value.write() = 17;
+        assert_eq!(value.read().as_ref(), &17);
+    }
+
+    #[test]
+    fn read_write_rwlock_ref() {
+        let lock = RwLock::new(42);
+        let mut value = OwnOrSpin::from(&lock);
+        *value.write() += 1;
+        assert_eq!(*value.read(), 43);
+        assert_eq!(*lock.read(), 43);
+    }
+
+    #[test]
+    fn try_lock_would_spin() {
+        let mutex = Mutex::new(42);
+        let mut value = OwnOrSpin::from(&mutex);
+        let guard = mutex.lock();
+        assert!(value.try_read().is_none());
+        assert!(value.try_write().is_none());
+        drop(guard);
+        assert_eq!(value.try_write().map(|value| *value), Some(42));
+
+        let mut owned = OwnOrSpin::new_mutex(42);
+        assert!(owned.try_write().is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn format_while_locked() {
+        let value = OwnOrSpin::new_mutex(42);
+        assert_eq!(format!("{}", value), "42");
+        let _guard = value.read();
+        assert_eq!(format!("{:?}", value), "<locked>");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn share_across_threads() {
+        let lock = RwLock::new(0);
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    let mut value = OwnOrSpin::from(&lock);
+                    *value.write() += 1;
+                });
+            }
+        });
+        assert_eq!(*lock.read(), 4);
+    }
+}
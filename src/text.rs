@@ -0,0 +1,494 @@
+//! String and byte buffer conveniences.
+
+use crate::{OwnOrBorrow, OwnOrBorrowUnsized, Reference};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Add;
+
+/// An [`OwnOrBorrow`] for text.
+///
+/// The guards dereference to [`String`], so deref coercion turns them into a `&str` and
+/// mutable access provides [`String::push_str`] and friends directly:
+///
+/// ```
+/// use own_or_borrow::OwnOrBorrowStr;
+///
+/// let mut value = OwnOrBorrowStr::from("hello");
+/// value.borrow_mut().push_str(", world");
+///
+/// let text: &str = &value.borrow();
+/// assert_eq!(text, "hello, world");
+/// assert_eq!(value, "hello, world");
+/// ```
+///
+/// The guards deliberately do not implement `AsRef<str>`, as `as_ref()` calls on them would
+/// then need type annotations.
+///
+/// Since the wrapper holds a [`String`], converting a `&str` copies it. Use
+/// [`OwnOrBorrowUnsizedStr`](crate::OwnOrBorrowUnsizedStr) to borrow string slices instead.
+pub type OwnOrBorrowStr<'a> = OwnOrBorrow<'a, String>;
+
+/// An [`OwnOrBorrow`] for byte buffers.
+///
+/// The guards dereference to [`Vec<u8>`], so deref coercion turns them into a `&[u8]`.
+/// Converting a `&[u8]` copies it; use
+/// [`OwnOrBorrowUnsizedBytes`](crate::OwnOrBorrowUnsizedBytes) to borrow byte slices instead.
+///
+/// ```
+/// use own_or_borrow::OwnOrBorrowBytes;
+///
+/// let mut value = OwnOrBorrowBytes::from(&b"abc"[..]);
+/// value.borrow_mut().extend_from_slice(b"def");
+///
+/// let bytes: &[u8] = &value.borrow();
+/// assert_eq!(bytes, b"abcdef");
+/// ```
+pub type OwnOrBorrowBytes<'a> = OwnOrBorrow<'a, Vec<u8>>;
+
+impl<'a> OwnOrBorrow<'a, String> {
+    /// Appends a string slice, promoting a plain shared reference to an owned copy first.
+    ///
+    /// Unlike `borrow_mut().push_str(..)`, this does not panic for
+    /// [`OwnOrBorrow::Borrowed`] values, similar to [`Cow::to_mut`](alloc::borrow::Cow::to_mut).
+    ///
+    /// ```
+    /// use own_or_borrow::OwnOrBorrowStr;
+    ///
+    /// let text = String::from("hello");
+    /// let mut value = OwnOrBorrowStr::borrowed(&text);
+    /// value.push_str(", world");
+    ///
+    /// assert_eq!(value, "hello, world");
+    /// assert_eq!(text, "hello");
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed.
+    #[track_caller]
+    pub fn push_str(&mut self, string: &str) {
+        if let OwnOrBorrow::Borrowed(value) = self {
+            *self = OwnOrBorrow::Owned(String::clone(value));
+        }
+        self.borrow_mut().push_str(string);
+    }
+
+    /// Borrows the text with leading and trailing whitespace removed.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn trim(&self) -> Reference<'_, str> {
+        Reference::map(self.borrow(), |text| text.trim())
+    }
+
+    /// Borrows the text with leading whitespace removed.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn trim_start(&self) -> Reference<'_, str> {
+        Reference::map(self.borrow(), |text| text.trim_start())
+    }
+
+    /// Borrows the text with trailing whitespace removed.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn trim_end(&self) -> Reference<'_, str> {
+        Reference::map(self.borrow(), |text| text.trim_end())
+    }
+
+    /// Returns an iterator over the substrings separated by characters matching `pattern`.
+    ///
+    /// The substrings are [`Reference`] guards into the original storage, so nothing is
+    /// copied. For [`RefCell`](core::cell::RefCell) backed values, the iterator and each
+    /// substring hold a shared borrow until they are dropped.
+    ///
+    /// ```
+    /// use own_or_borrow::OwnOrBorrowStr;
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(String::from("a,b,,c"));
+    /// let value = OwnOrBorrowStr::from(&cell);
+    /// let fields: Vec<_> = value.split(|c| c == ',').collect();
+    ///
+    /// assert_eq!(fields.len(), 4);
+    /// assert_eq!(&*fields[3], "c");
+    /// assert!(cell.try_borrow_mut().is_err());
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn split<P>(&self, pattern: P) -> StrSplit<'_, P>
+    where
+        P: FnMut(char) -> bool,
+    {
+        self.splitn(usize::MAX, pattern)
+    }
+
+    /// Returns an iterator over at most `n` substrings separated by characters matching
+    /// `pattern`, like [`str::splitn`]. The last substring contains the remaining text.
+    ///
+    /// ```
+    /// use own_or_borrow::OwnOrBorrowStr;
+    ///
+    /// let value = OwnOrBorrowStr::from("key=value=42");
+    /// let mut parts = value.splitn(2, |c| c == '=');
+    ///
+    /// assert_eq!(&*parts.next().unwrap(), "key");
+    /// assert_eq!(&*parts.next().unwrap(), "value=42");
+    /// assert!(parts.next().is_none());
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn splitn<P>(&self, n: usize, pattern: P) -> StrSplit<'_, P>
+    where
+        P: FnMut(char) -> bool,
+    {
+        StrSplit {
+            rest: Some(Reference::map(self.borrow(), String::as_str)).filter(|_| n != 0),
+            pattern,
+            remaining: n,
+        }
+    }
+
+    /// Splits the text at the first character matching `pattern`, like [`str::split_once`].
+    /// The matching character is not included.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn split_once<P>(&self, pattern: P) -> Option<(Reference<'_, str>, Reference<'_, str>)>
+    where
+        P: FnMut(char) -> bool,
+    {
+        let mut parts = self.splitn(2, pattern);
+        match (parts.next(), parts.next()) {
+            (Some(first), Some(second)) => Some((first, second)),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over the whitespace-separated words of the text, like
+    /// [`str::split_whitespace`]. The words are [`Reference`] guards into the original
+    /// storage, as for [`split`](Self::split).
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn split_whitespace(&self) -> StrSplitWhitespace<'_> {
+        StrSplitWhitespace {
+            rest: Some(Reference::map(self.borrow(), String::as_str)),
+        }
+    }
+
+    /// Returns an iterator over the lines of the text, like [`str::lines`].
+    ///
+    /// Lines end with either `\n` or `\r\n`, and the line endings are not included. The
+    /// lines are [`Reference`] guards into the original storage, as for
+    /// [`split`](Self::split).
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn lines(&self) -> StrLines<'_> {
+        StrLines {
+            rest: Some(Reference::map(self.borrow(), String::as_str)),
+        }
+    }
+}
+
+impl<'a> Add<&str> for OwnOrBorrow<'a, String> {
+    type Output = Self;
+
+    /// Concatenates the string slice using [`push_str`](OwnOrBorrow::push_str).
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed.
+    #[inline]
+    #[track_caller]
+    fn add(mut self, rhs: &str) -> Self::Output {
+        self.push_str(rhs);
+        self
+    }
+}
+
+/// An iterator over the substrings of an [`OwnOrBorrowStr`] separated by characters matching
+/// a predicate, as returned by [`OwnOrBorrow::split`] and [`OwnOrBorrow::splitn`].
+pub struct StrSplit<'b, P> {
+    rest: Option<Reference<'b, str>>,
+    pattern: P,
+    remaining: usize,
+}
+
+impl<'b, P> Iterator for StrSplit<'b, P>
+where
+    P: FnMut(char) -> bool,
+{
+    type Item = Reference<'b, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest.take()?;
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            return Some(rest);
+        }
+        match rest.char_indices().find(|&(_, c)| (self.pattern)(c)) {
+            Some((index, separator)) => {
+                let (token, rest) = Reference::map_split(rest, |text| {
+                    (&text[..index], &text[index + separator.len_utf8()..])
+                });
+                self.rest = Some(rest);
+                Some(token)
+            }
+            None => Some(rest),
+        }
+    }
+}
+
+/// An iterator over the whitespace-separated words of an [`OwnOrBorrowStr`], as returned by
+/// [`OwnOrBorrow::split_whitespace`].
+pub struct StrSplitWhitespace<'b> {
+    rest: Option<Reference<'b, str>>,
+}
+
+impl<'b> Iterator for StrSplitWhitespace<'b> {
+    type Item = Reference<'b, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = Reference::map(self.rest.take()?, str::trim_start);
+        if rest.is_empty() {
+            return None;
+        }
+        match rest.find(char::is_whitespace) {
+            Some(index) => {
+                let (word, rest) = Reference::map_split(rest, |text| text.split_at(index));
+                self.rest = Some(rest);
+                Some(word)
+            }
+            None => Some(rest),
+        }
+    }
+}
+
+/// An iterator over the lines of an [`OwnOrBorrowStr`], as returned by
+/// [`OwnOrBorrow::lines`].
+pub struct StrLines<'b> {
+    rest: Option<Reference<'b, str>>,
+}
+
+impl<'b> Iterator for StrLines<'b> {
+    type Item = Reference<'b, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest.take().filter(|rest| !rest.is_empty())?;
+        match rest.find('\n') {
+            Some(index) => {
+                let (line, rest) = Reference::map_split(rest, |text| {
+                    let line = &text[..index];
+                    (line.strip_suffix('\r').unwrap_or(line), &text[index + 1..])
+                });
+                self.rest = Some(rest);
+                Some(line)
+            }
+            None => Some(rest),
+        }
+    }
+}
+
+impl<'a> From<&str> for OwnOrBorrow<'a, String> {
+    /// Creates an [`OwnOrBorrow::Owned`] copy of the string.
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::Owned(String::from(value))
+    }
+}
+
+impl<'a> From<String> for OwnOrBorrow<'a, String> {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::Owned(value)
+    }
+}
+
+impl<'a> From<&[u8]> for OwnOrBorrow<'a, Vec<u8>> {
+    /// Creates an [`OwnOrBorrow::Owned`] copy of the bytes.
+    #[inline]
+    fn from(value: &[u8]) -> Self {
+        Self::Owned(Vec::from(value))
+    }
+}
+
+impl<'a> From<Vec<u8>> for OwnOrBorrow<'a, Vec<u8>> {
+    #[inline]
+    fn from(value: Vec<u8>) -> Self {
+        Self::Owned(value)
+    }
+}
+
+impl<'a> From<String> for OwnOrBorrowUnsized<'a, str> {
+    /// Moves the string into an [`OwnOrBorrowUnsized::Boxed`] value without copying it.
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::Boxed(value.into_boxed_str())
+    }
+}
+
+impl<'a, T> From<Vec<T>> for OwnOrBorrowUnsized<'a, [T]> {
+    /// Moves the vector into an [`OwnOrBorrowUnsized::Boxed`] value.
+    #[inline]
+    fn from(value: Vec<T>) -> Self {
+        Self::Boxed(value.into_boxed_slice())
+    }
+}
+
+macro_rules! impl_unsized_eq {
+    ($owned:ty, $target:ty) => {
+        impl<'a> PartialEq<$target> for OwnOrBorrow<'a, $owned> {
+            /// ## Panics
+            /// Panics if the value is currently mutably borrowed.
+            fn eq(&self, other: &$target) -> bool {
+                **self.borrow() == *other
+            }
+        }
+
+        impl<'a, 'b> PartialEq<&'b $target> for OwnOrBorrow<'a, $owned> {
+            /// ## Panics
+            /// Panics if the value is currently mutably borrowed.
+            fn eq(&self, other: &&'b $target) -> bool {
+                **self.borrow() == **other
+            }
+        }
+    };
+}
+
+impl_unsized_eq!(String, str);
+impl_unsized_eq!(Vec<u8>, [u8]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    #[test]
+    fn str_from_literal() {
+        let value = OwnOrBorrowStr::from("hello");
+        assert!(matches!(value, OwnOrBorrow::Owned(_)));
+        assert_eq!(value.borrow().len(), 5);
+        assert_eq!(value, "hello");
+    }
+
+    #[test]
+    fn str_push_through_refcell() {
+        let cell = RefCell::new(String::from("a"));
+        let mut value = OwnOrBorrowStr::from(&cell);
+        value.borrow_mut().push_str("bc");
+        assert_eq!(value.borrow().as_str(), "abc");
+        assert_eq!(*cell.borrow(), "abc");
+    }
+
+    #[test]
+    fn push_str_promotes_borrowed_text() {
+        let text = String::from("a");
+        let mut value = OwnOrBorrowStr::borrowed(&text);
+        value.push_str("b");
+        assert!(matches!(value, OwnOrBorrow::Owned(_)));
+
+        let value = value + "c";
+        assert_eq!(value, "abc");
+        assert_eq!(text, "a");
+    }
+
+    #[test]
+    fn trim_projections() {
+        let cell = RefCell::new(String::from("  hi  "));
+        let value = OwnOrBorrowStr::from(&cell);
+        assert_eq!(&*value.trim(), "hi");
+        assert_eq!(&*value.trim_start(), "hi  ");
+        assert_eq!(&*value.trim_end(), "  hi");
+    }
+
+    #[test]
+    fn split_yields_borrowed_substrings() {
+        let value = OwnOrBorrowStr::from("a,b,,c");
+        let base = value.borrow().as_ptr();
+        let fields: Vec<_> = value.split(|c| c == ',').collect();
+        let fields: Vec<&str> = fields.iter().map(|field| &**field).collect();
+        assert_eq!(fields, ["a", "b", "", "c"]);
+        assert!(core::ptr::eq(fields[3].as_ptr(), base.wrapping_add(5)));
+    }
+
+    #[test]
+    fn split_holds_refcell_borrow() {
+        let cell = RefCell::new(String::from("a b"));
+        let value = OwnOrBorrowStr::from(&cell);
+        let mut tokens = value.split(|c| c == ' ');
+        let first = tokens.next().expect("token exists");
+        assert!(cell.try_borrow_mut().is_err());
+        drop(tokens);
+        drop(first);
+        assert!(cell.try_borrow_mut().is_ok());
+    }
+
+    #[test]
+    fn splitn_and_split_once() {
+        let value = OwnOrBorrowStr::from("a:b:c");
+        for n in 0..5 {
+            let parts: Vec<_> = value.splitn(n, |c| c == ':').collect();
+            let parts: Vec<&str> = parts.iter().map(|part| &**part).collect();
+            assert_eq!(parts, "a:b:c".splitn(n, ':').collect::<Vec<_>>(), "{n}");
+        }
+
+        let (key, rest) = value.split_once(|c| c == ':').expect("separator exists");
+        assert_eq!((&*key, &*rest), ("a", "b:c"));
+        assert!(value.split_once(|c| c == '=').is_none());
+    }
+
+    #[test]
+    fn split_whitespace_matches_str() {
+        for text in ["", "   ", "a", " a  b\tc\n", "\u{3000}x y"] {
+            let value = OwnOrBorrowStr::from(text);
+            let words: Vec<_> = value.split_whitespace().collect();
+            let words: Vec<&str> = words.iter().map(|word| &**word).collect();
+            assert_eq!(
+                words,
+                text.split_whitespace().collect::<Vec<_>>(),
+                "{text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn lines_match_str_lines() {
+        for text in ["", "a", "a\n", "a\r\nb\n\nc", "a\rb\r", "\n\n"] {
+            let value = OwnOrBorrowStr::from(text);
+            let lines: Vec<_> = value.lines().collect();
+            let lines: Vec<&str> = lines.iter().map(|line| &**line).collect();
+            assert_eq!(lines, text.lines().collect::<Vec<_>>(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn unsized_from_owned_buffers() {
+        let value = OwnOrBorrowUnsized::<str>::from(String::from("hello"));
+        assert!(matches!(value, OwnOrBorrowUnsized::Boxed(_)));
+        assert_eq!(&*value.borrow(), "hello");
+
+        let value = OwnOrBorrowUnsized::<[u8]>::from(Vec::from([1, 2, 3]));
+        assert!(matches!(value, OwnOrBorrowUnsized::Boxed(_)));
+        assert_eq!(&*value.borrow(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn bytes_compare_with_slices() {
+        let value = OwnOrBorrowBytes::from(Vec::from([1, 2, 3]));
+        assert_eq!(value, &[1u8, 2, 3][..]);
+        assert!(value != [4u8][..]);
+        let bytes: &[u8] = &value.borrow();
+        assert_eq!(bytes.len(), 3);
+    }
+}
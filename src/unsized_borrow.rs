@@ -0,0 +1,617 @@
+//! Counterpart to [`OwnOrBorrow`](crate::OwnOrBorrow) for dynamically sized types.
+
+use crate::audit::{self, Source, Wrapper};
+use crate::{BorrowFault, Reference, ReferenceMut};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+use core::cell::RefCell;
+
+/// A type that provides either a reference or a [`RefCell`] borrowed reference to a possibly
+/// unsized value, such as a trait object or a slice.
+///
+/// Since unsized values cannot be stored inline, [`OwnOrBorrow`](crate::OwnOrBorrow)'s
+/// `Owned` and `RefCell` variants are replaced by `OwnOrBorrowUnsized::Boxed` here.
+/// Borrowing returns the regular [`Reference`] and [`ReferenceMut`] guards.
+///
+/// ```
+/// use own_or_borrow::OwnOrBorrowUnsized;
+/// use core::cell::RefCell;
+///
+/// let cell = RefCell::new([1u8, 2, 3]);
+/// let mut value = OwnOrBorrowUnsized::<[u8]>::from(&cell as &RefCell<[u8]>);
+/// value.borrow_mut()[0] = 42;
+///
+/// assert_eq!(&*value.borrow(), &[42, 2, 3]);
+/// ```
+pub enum OwnOrBorrowUnsized<'a, T: ?Sized> {
+    /// A reference to a [`RefCell`].
+    RefCellRef(&'a RefCell<T>),
+    /// A plain shared reference. The value cannot be borrowed mutably.
+    Borrowed(&'a T),
+    /// A plain mutable reference.
+    BorrowedMut(&'a mut T),
+    /// An owned, boxed value.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    Boxed(Box<T>),
+    /// A shared, reference-counted [`RefCell`]. The wrapper keeps the value alive.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    RefCellRc(Rc<RefCell<T>>),
+}
+
+impl<'a, T: ?Sized> OwnOrBorrowUnsized<'a, T> {
+    /// Initializes a new instance from a plain shared reference.
+    ///
+    /// Since the value is only shared, [`borrow_mut`](Self::borrow_mut) will panic and
+    /// [`try_borrow_mut`](Self::try_borrow_mut) will fail with [`BorrowFault::Shared`].
+    pub const fn borrowed(value: &'a T) -> Self {
+        Self::Borrowed(value)
+    }
+
+    /// Initializes a new instance from a plain mutable reference.
+    pub const fn borrowed_mut(value: &'a mut T) -> Self {
+        Self::BorrowedMut(value)
+    }
+
+    /// Borrows the inner value.
+    #[track_caller]
+    pub fn borrow(&self) -> Reference<'_, T> {
+        match self {
+            OwnOrBorrowUnsized::RefCellRef(ref_cell) => Reference::RefCellRef(
+                audit::borrow(Wrapper::OwnOrBorrowUnsized, ref_cell, Source::RefCellRef),
+                ref_cell,
+            ),
+            OwnOrBorrowUnsized::Borrowed(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrowUnsized, Source::Borrowed, false);
+                Reference::Borrowed(value)
+            }
+            OwnOrBorrowUnsized::BorrowedMut(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrowUnsized, Source::BorrowedMut, false);
+                Reference::Borrowed(value)
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrowUnsized::Boxed(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrowUnsized, Source::Boxed, false);
+                Reference::Borrowed(value)
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrowUnsized::RefCellRc(ref_cell) => Reference::RefCellRef(
+                audit::borrow(Wrapper::OwnOrBorrowUnsized, ref_cell, Source::RefCellRc),
+                ref_cell,
+            ),
+        }
+    }
+
+    /// Borrows the inner value mutably.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed, or if it is a plain shared reference
+    /// ([`OwnOrBorrowUnsized::Borrowed`]).
+    #[track_caller]
+    pub fn borrow_mut(&mut self) -> ReferenceMut<'_, T> {
+        match self {
+            OwnOrBorrowUnsized::RefCellRef(ref_cell) => ReferenceMut::RefCellRef(
+                audit::borrow_mut(Wrapper::OwnOrBorrowUnsized, ref_cell, Source::RefCellRef),
+                ref_cell,
+            ),
+            OwnOrBorrowUnsized::Borrowed(_) => panic!("{}", BorrowFault::Shared),
+            OwnOrBorrowUnsized::BorrowedMut(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrowUnsized, Source::BorrowedMut, true);
+                ReferenceMut::Borrowed(value)
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrowUnsized::Boxed(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrowUnsized, Source::Boxed, true);
+                ReferenceMut::Borrowed(value)
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrowUnsized::RefCellRc(ref_cell) => ReferenceMut::RefCellRef(
+                audit::borrow_mut(Wrapper::OwnOrBorrowUnsized, ref_cell, Source::RefCellRc),
+                ref_cell,
+            ),
+        }
+    }
+
+    /// Attempts to borrow the inner value.
+    ///
+    /// Unlike [`borrow`](Self::borrow), this returns an error instead of panicking if the
+    /// value is currently mutably borrowed.
+    pub fn try_borrow(&self) -> Result<Reference<'_, T>, BorrowFault> {
+        match self {
+            OwnOrBorrowUnsized::RefCellRef(ref_cell) => Ok(Reference::RefCellRef(
+                audit::try_borrow(Wrapper::OwnOrBorrowUnsized, ref_cell, Source::RefCellRef)?,
+                ref_cell,
+            )),
+            OwnOrBorrowUnsized::Borrowed(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrowUnsized, Source::Borrowed, false);
+                Ok(Reference::Borrowed(value))
+            }
+            OwnOrBorrowUnsized::BorrowedMut(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrowUnsized, Source::BorrowedMut, false);
+                Ok(Reference::Borrowed(value))
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrowUnsized::Boxed(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrowUnsized, Source::Boxed, false);
+                Ok(Reference::Borrowed(value))
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrowUnsized::RefCellRc(ref_cell) => Ok(Reference::RefCellRef(
+                audit::try_borrow(Wrapper::OwnOrBorrowUnsized, ref_cell, Source::RefCellRc)?,
+                ref_cell,
+            )),
+        }
+    }
+
+    /// Attempts to borrow the inner value mutably.
+    ///
+    /// Unlike [`borrow_mut`](Self::borrow_mut), this returns an error instead of panicking if
+    /// the value is currently borrowed or is a plain shared reference.
+    pub fn try_borrow_mut(&mut self) -> Result<ReferenceMut<'_, T>, BorrowFault> {
+        match self {
+            OwnOrBorrowUnsized::RefCellRef(ref_cell) => Ok(ReferenceMut::RefCellRef(
+                audit::try_borrow_mut(Wrapper::OwnOrBorrowUnsized, ref_cell, Source::RefCellRef)?,
+                ref_cell,
+            )),
+            OwnOrBorrowUnsized::Borrowed(_) => Err(BorrowFault::Shared),
+            OwnOrBorrowUnsized::BorrowedMut(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrowUnsized, Source::BorrowedMut, true);
+                Ok(ReferenceMut::Borrowed(value))
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrowUnsized::Boxed(value) => {
+                audit::unchecked(Wrapper::OwnOrBorrowUnsized, Source::Boxed, true);
+                Ok(ReferenceMut::Borrowed(value))
+            }
+            #[cfg(feature = "alloc")]
+            OwnOrBorrowUnsized::RefCellRc(ref_cell) => Ok(ReferenceMut::RefCellRef(
+                audit::try_borrow_mut(Wrapper::OwnOrBorrowUnsized, ref_cell, Source::RefCellRc)?,
+                ref_cell,
+            )),
+        }
+    }
+}
+
+impl<'a, T> OwnOrBorrowUnsized<'a, [T]> {
+    /// Borrows the slice as two guards split at `mid`, like [`slice::split_at`].
+    ///
+    /// ## Panics
+    /// Panics if `mid > len`, or if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn split_at(&self, mid: usize) -> (Reference<'_, [T]>, Reference<'_, [T]>) {
+        Reference::map_split(self.borrow(), |slice| slice.split_at(mid))
+    }
+
+    /// Borrows the slice mutably as two guards split at `mid`, like
+    /// [`slice::split_at_mut`].
+    ///
+    /// ## Panics
+    /// Panics if `mid > len`, if the value is currently borrowed, or if it is a plain shared
+    /// reference ([`OwnOrBorrowUnsized::Borrowed`]).
+    #[track_caller]
+    pub fn split_at_mut(&mut self, mid: usize) -> (ReferenceMut<'_, [T]>, ReferenceMut<'_, [T]>) {
+        ReferenceMut::map_split(self.borrow_mut(), |slice| slice.split_at_mut(mid))
+    }
+
+    /// Returns an iterator over `chunk_size` elements of the slice at a time, like
+    /// [`slice::chunks`]. The last chunk is shorter if the length is not a multiple of
+    /// `chunk_size`.
+    ///
+    /// The chunks are [`Reference`] guards into the original storage, so borrowed and
+    /// boxed slices are handled the same way. For [`RefCell`] backed values, the iterator and
+    /// each chunk hold a shared borrow until they are dropped.
+    ///
+    /// ```
+    /// use own_or_borrow::OwnOrBorrowUnsized;
+    ///
+    /// let frame = [0xAAu8, 1, 2, 0xAA, 3];
+    /// let value = OwnOrBorrowUnsized::from(&frame[..]);
+    /// let chunks: Vec<_> = value.chunks(2).collect();
+    ///
+    /// assert_eq!(chunks.len(), 3);
+    /// assert_eq!(&*chunks[2], &[3]);
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if `chunk_size` is zero, or if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn chunks(&self, chunk_size: usize) -> SliceChunks<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        SliceChunks {
+            rest: Some(self.borrow()),
+            chunk_size,
+        }
+    }
+
+    /// Returns an iterator over mutable chunks of `chunk_size` elements, like
+    /// [`slice::chunks_mut`].
+    ///
+    /// ## Panics
+    /// Panics if `chunk_size` is zero, if the value is currently borrowed, or if it is a
+    /// plain shared reference ([`OwnOrBorrowUnsized::Borrowed`]).
+    #[track_caller]
+    pub fn chunks_mut(&mut self, chunk_size: usize) -> SliceChunksMut<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        SliceChunksMut {
+            rest: Some(self.borrow_mut()),
+            chunk_size,
+        }
+    }
+
+    /// Returns an iterator over all overlapping windows of `size` elements, like
+    /// [`slice::windows`].
+    ///
+    /// The windows are [`Reference`] guards into the original storage, as for
+    /// [`chunks`](Self::chunks).
+    ///
+    /// ## Panics
+    /// Panics if `size` is zero, or if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn windows(&self, size: usize) -> SliceWindows<'_, T> {
+        assert!(size != 0, "window size must be non-zero");
+        SliceWindows {
+            slice: self.borrow(),
+            start: 0,
+            size,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T> OwnOrBorrowUnsized<'a, [T]> {
+    /// Initializes a new instance that owns a boxed slice of `len` elements, each created by
+    /// calling `f` with its index.
+    ///
+    /// The elements are written directly into the heap allocation, so large buffers never
+    /// have to fit on the stack, unlike an array passed to
+    /// [`OwnOrBorrow::own`](crate::OwnOrBorrow::own).
+    ///
+    /// ```
+    /// use own_or_borrow::OwnOrBorrowUnsized;
+    ///
+    /// let mut buffer = OwnOrBorrowUnsized::<[u8]>::boxed_from_fn(64 * 1024, |_| 0);
+    /// buffer.borrow_mut()[0] = 42;
+    /// assert_eq!(buffer.borrow().len(), 64 * 1024);
+    /// ```
+    pub fn boxed_from_fn<F>(len: usize, f: F) -> Self
+    where
+        F: FnMut(usize) -> T,
+    {
+        Self::Boxed((0..len).map(f).collect())
+    }
+}
+
+impl<'a, T: ?Sized> From<&'a RefCell<T>> for OwnOrBorrowUnsized<'a, T> {
+    #[inline]
+    fn from(value: &'a RefCell<T>) -> Self {
+        Self::RefCellRef(value)
+    }
+}
+
+impl<'a, T: ?Sized> From<&'a mut T> for OwnOrBorrowUnsized<'a, T> {
+    #[inline]
+    fn from(value: &'a mut T) -> Self {
+        Self::BorrowedMut(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized> From<Box<T>> for OwnOrBorrowUnsized<'a, T> {
+    #[inline]
+    fn from(value: Box<T>) -> Self {
+        Self::Boxed(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized> From<Rc<RefCell<T>>> for OwnOrBorrowUnsized<'a, T> {
+    #[inline]
+    fn from(value: Rc<RefCell<T>>) -> Self {
+        Self::RefCellRc(value)
+    }
+}
+
+/// An iterator over chunks of an [`OwnOrBorrowUnsized`] slice, as returned by
+/// [`OwnOrBorrowUnsized::chunks`].
+pub struct SliceChunks<'b, T> {
+    rest: Option<Reference<'b, [T]>>,
+    chunk_size: usize,
+}
+
+impl<'b, T> Iterator for SliceChunks<'b, T> {
+    type Item = Reference<'b, [T]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest.take().filter(|rest| !rest.is_empty())?;
+        if rest.len() <= self.chunk_size {
+            return Some(rest);
+        }
+        let (chunk, rest) = Reference::map_split(rest, |slice| slice.split_at(self.chunk_size));
+        self.rest = Some(rest);
+        Some(chunk)
+    }
+}
+
+/// An iterator over mutable chunks of an [`OwnOrBorrowUnsized`] slice, as returned by
+/// [`OwnOrBorrowUnsized::chunks_mut`].
+pub struct SliceChunksMut<'b, T> {
+    rest: Option<ReferenceMut<'b, [T]>>,
+    chunk_size: usize,
+}
+
+impl<'b, T> Iterator for SliceChunksMut<'b, T> {
+    type Item = ReferenceMut<'b, [T]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest.take().filter(|rest| !rest.is_empty())?;
+        if rest.len() <= self.chunk_size {
+            return Some(rest);
+        }
+        let chunk_size = self.chunk_size;
+        let (chunk, rest) = ReferenceMut::map_split(rest, |slice| slice.split_at_mut(chunk_size));
+        self.rest = Some(rest);
+        Some(chunk)
+    }
+}
+
+/// An iterator over overlapping windows of an [`OwnOrBorrowUnsized`] slice, as returned by
+/// [`OwnOrBorrowUnsized::windows`].
+pub struct SliceWindows<'b, T> {
+    slice: Reference<'b, [T]>,
+    start: usize,
+    size: usize,
+}
+
+impl<'b, T> Iterator for SliceWindows<'b, T> {
+    type Item = Reference<'b, [T]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end) = (self.start, self.start + self.size);
+        if end > self.slice.len() {
+            return None;
+        }
+        self.start += 1;
+        Some(Reference::map(Reference::clone(&self.slice), |slice| {
+            &slice[start..end]
+        }))
+    }
+}
+
+/// An [`OwnOrBorrowUnsized`] for text that borrows string slices without copying them.
+///
+/// Unlike `OwnOrBorrowStr`, which has to copy a `&str` into an owned `String`, converting a
+/// `&'a str` yields the [`OwnOrBorrowUnsized::Borrowed`] variant and needs no allocator:
+///
+/// ```
+/// use own_or_borrow::{OwnOrBorrowUnsized, OwnOrBorrowUnsizedStr};
+///
+/// fn greet(name: &str) -> OwnOrBorrowUnsizedStr<'_> {
+///     OwnOrBorrowUnsizedStr::from(name)
+/// }
+///
+/// let value = greet("world");
+/// assert!(matches!(value, OwnOrBorrowUnsized::Borrowed(_)));
+/// assert_eq!(&*value.borrow(), "world");
+/// ```
+pub type OwnOrBorrowUnsizedStr<'a> = OwnOrBorrowUnsized<'a, str>;
+
+/// An [`OwnOrBorrowUnsized`] for bytes that borrows byte slices without copying them.
+///
+/// Converting a `&'a [u8]` yields the [`OwnOrBorrowUnsized::Borrowed`] variant, while
+/// `OwnOrBorrowBytes` copies it into an owned buffer.
+///
+/// ```
+/// use own_or_borrow::OwnOrBorrowUnsizedBytes;
+///
+/// let value = OwnOrBorrowUnsizedBytes::from(&b"abc"[..]);
+/// assert_eq!(&*value.borrow(), b"abc");
+/// ```
+pub type OwnOrBorrowUnsizedBytes<'a> = OwnOrBorrowUnsized<'a, [u8]>;
+
+impl<'a> From<&'a str> for OwnOrBorrowUnsized<'a, str> {
+    #[inline]
+    fn from(value: &'a str) -> Self {
+        Self::Borrowed(value)
+    }
+}
+
+impl<'a, T> From<&'a [T]> for OwnOrBorrowUnsized<'a, [T]> {
+    #[inline]
+    fn from(value: &'a [T]) -> Self {
+        Self::Borrowed(value)
+    }
+}
+
+impl<'a, T> core::fmt::Debug for OwnOrBorrowUnsized<'a, T>
+where
+    T: core::fmt::Debug + ?Sized,
+{
+    /// Formats the inner value, or prints `<mutably borrowed>` if it is currently mutably
+    /// borrowed.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.try_borrow() {
+            Ok(data) => core::fmt::Debug::fmt(&*data, f),
+            Err(_) => f.write_str("<mutably borrowed>"),
+        }
+    }
+}
+
+impl<'a, T> core::fmt::Display for OwnOrBorrowUnsized<'a, T>
+where
+    T: core::fmt::Display + ?Sized,
+{
+    /// Formats the inner value, or prints `<mutably borrowed>` if it is currently mutably
+    /// borrowed.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.try_borrow() {
+            Ok(data) => core::fmt::Display::fmt(&*data, f),
+            Err(_) => f.write_str("<mutably borrowed>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    trait Counter {
+        fn increment(&mut self);
+        fn count(&self) -> u32;
+    }
+
+    struct Simple(u32);
+
+    impl Counter for Simple {
+        fn increment(&mut self) {
+            self.0 += 1;
+        }
+
+        fn count(&self) -> u32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn borrow_slice_refcell_ref() {
+        let cell = RefCell::new([1u8, 2, 3]);
+        let mut value = OwnOrBorrowUnsized::<[u8]>::from(&cell as &RefCell<[u8]>);
+        value.borrow_mut()[1] = 42;
+        assert_eq!(&*value.borrow(), &[1, 42, 3]);
+        assert_eq!(cell.borrow()[1], 42);
+    }
+
+    #[test]
+    fn borrow_trait_object_mut() {
+        let mut counter = Simple(0);
+        let mut value = OwnOrBorrowUnsized::<dyn Counter>::from(&mut counter as &mut dyn Counter);
+        value.borrow_mut().increment();
+        assert_eq!(value.borrow().count(), 1);
+        assert_eq!(counter.0, 1);
+    }
+
+    #[test]
+    fn borrow_str_shared() {
+        let mut value = OwnOrBorrowUnsized::borrowed("hello");
+        assert_eq!(&*value.borrow(), "hello");
+        assert!(matches!(value.try_borrow_mut(), Err(BorrowFault::Shared)));
+    }
+
+    #[test]
+    fn str_and_slices_convert_without_copying() {
+        let text = "hello";
+        let value = OwnOrBorrowUnsizedStr::from(text);
+        assert!(
+            matches!(value, OwnOrBorrowUnsized::Borrowed(borrowed) if core::ptr::eq(borrowed, text))
+        );
+
+        let bytes = [1u8, 2, 3];
+        let value = OwnOrBorrowUnsizedBytes::from(&bytes[..]);
+        assert!(
+            matches!(value, OwnOrBorrowUnsized::Borrowed(borrowed) if core::ptr::eq(borrowed, &bytes[..]))
+        );
+    }
+
+    #[test]
+    fn split_slice_refcell_ref() {
+        let cell = RefCell::new([1u8, 2, 3, 4]);
+        let mut value = OwnOrBorrowUnsized::<[u8]>::from(&cell as &RefCell<[u8]>);
+        {
+            let (mut header, mut payload) = value.split_at_mut(1);
+            header[0] = 0xAA;
+            payload[0] = 42;
+        }
+        let (header, payload) = value.split_at(1);
+        assert_eq!(&*header, &[0xAA]);
+        assert_eq!(&*payload, &[42, 3, 4]);
+        assert!(cell.try_borrow_mut().is_err());
+    }
+
+    #[test]
+    fn chunks_and_windows_match_slices() {
+        let data = [1u8, 2, 3, 4, 5];
+        let value = OwnOrBorrowUnsized::from(&data[..]);
+        assert_eq!(value.chunks(2).count(), 3);
+        for (chunk, expected) in value.chunks(2).zip(data.chunks(2)) {
+            assert_eq!(&*chunk, expected);
+        }
+
+        assert_eq!(value.windows(3).count(), 3);
+        for (window, expected) in value.windows(3).zip(data.windows(3)) {
+            assert_eq!(&*window, expected);
+        }
+        assert_eq!(value.windows(6).count(), 0);
+    }
+
+    #[test]
+    fn chunks_mut_write_through() {
+        let mut data = [0u8; 5];
+        let mut value = OwnOrBorrowUnsized::from(&mut data[..]);
+        for (index, mut chunk) in value.chunks_mut(2).enumerate() {
+            chunk.fill(index as u8);
+        }
+        assert_eq!(data, [0, 0, 1, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunks_of_zero_panic() {
+        let data = [1u8];
+        let _ = OwnOrBorrowUnsized::from(&data[..]).chunks(0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn display_str() {
+        let value = OwnOrBorrowUnsized::borrowed("hello");
+        assert_eq!(format!("{}", value), "hello");
+        assert_eq!(format!("{:?}", value), "\"hello\"");
+    }
+
+    #[test]
+    fn try_borrow_mut_fails_while_borrowed() {
+        let cell = RefCell::new([0u8; 2]);
+        let mut value = OwnOrBorrowUnsized::<[u8]>::from(&cell as &RefCell<[u8]>);
+        let _guard = cell.borrow();
+        assert!(matches!(
+            value.try_borrow_mut(),
+            Err(BorrowFault::BorrowMut(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn borrow_boxed_trait_object() {
+        let mut value = OwnOrBorrowUnsized::<dyn Counter>::from(Box::new(Simple(1)) as Box<_>);
+        value.borrow_mut().increment();
+        assert_eq!(value.borrow().count(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn boxed_slice_from_fn() {
+        let value = OwnOrBorrowUnsized::<[usize]>::boxed_from_fn(4, |index| index * 2);
+        assert_eq!(&*value.borrow(), &[0, 2, 4, 6]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn borrow_rc_trait_object() {
+        let shared: Rc<RefCell<dyn Counter>> = Rc::new(RefCell::new(Simple(0)));
+        let mut value = OwnOrBorrowUnsized::from(shared.clone());
+        value.borrow_mut().increment();
+        assert_eq!(shared.borrow().count(), 1);
+    }
+
+    #[test]
+    fn map_reference_to_sub_slice() {
+        let value = OwnOrBorrowUnsized::borrowed(&[1u8, 2, 3][..]);
+        let tail = Reference::map(value.borrow(), |slice| &slice[1..]);
+        assert_eq!(&*tail, &[2, 3]);
+    }
+}
@@ -0,0 +1,192 @@
+//! Counterpart to [`OwnOrBorrow`](crate::OwnOrBorrow) that does not keep shared values alive.
+
+use crate::OwnOrBorrow;
+use alloc::rc::{Rc, Weak};
+use core::cell::RefCell;
+
+/// A type that provides either an owned value, a [`RefCell`] borrowed reference or a weak
+/// reference to a shared [`RefCell`].
+///
+/// The [`OwnOrWeak::RefCellWeak`] variant does not keep the value alive, which suits
+/// cache-like structures. Since the value may be gone, access goes through
+/// [`upgrade`](Self::upgrade) or [`with`](Self::with), which return `None` once the last
+/// strong reference was dropped:
+///
+/// ```
+/// use own_or_borrow::OwnOrWeak;
+/// use core::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// let shared = Rc::new(RefCell::new(42));
+/// let value = OwnOrWeak::downgrade(&shared);
+/// assert_eq!(value.with(|value| *value), Some(42));
+///
+/// drop(shared);
+/// assert!(value.upgrade().is_none());
+/// ```
+pub enum OwnOrWeak<'a, T> {
+    /// An owned value.
+    Owned(T),
+    /// A reference to a [`RefCell`].
+    RefCellRef(&'a RefCell<T>),
+    /// A shared, reference-counted [`RefCell`]. The wrapper keeps the value alive.
+    RefCellRc(Rc<RefCell<T>>),
+    /// A weak reference to a shared [`RefCell`]. The value may have been dropped.
+    RefCellWeak(Weak<RefCell<T>>),
+}
+
+impl<'a, T> OwnOrWeak<'a, T> {
+    /// Initializes a new instance that owns data.
+    pub const fn own(value: T) -> Self {
+        Self::Owned(value)
+    }
+
+    /// Initializes a new instance that weakly references the shared [`RefCell`].
+    pub fn downgrade(value: &Rc<RefCell<T>>) -> Self {
+        Self::RefCellWeak(Rc::downgrade(value))
+    }
+
+    /// Returns `false` if the value was dropped.
+    pub fn is_alive(&self) -> bool {
+        match self {
+            OwnOrWeak::RefCellWeak(weak) => weak.strong_count() > 0,
+            _ => true,
+        }
+    }
+
+    /// Provides shared access to the value through an [`OwnOrBorrow`], or returns `None` if
+    /// the value was dropped.
+    ///
+    /// For [`OwnOrWeak::RefCellWeak`] values, the returned wrapper holds a strong reference
+    /// and keeps the value alive until it is dropped. Owned values are lent as
+    /// [`OwnOrBorrow::Borrowed`] and cannot be borrowed mutably; use
+    /// [`upgrade_mut`](Self::upgrade_mut) for that.
+    pub fn upgrade(&self) -> Option<OwnOrBorrow<'_, T>> {
+        match self {
+            OwnOrWeak::Owned(value) => Some(OwnOrBorrow::Borrowed(value)),
+            OwnOrWeak::RefCellRef(ref_cell) => Some(OwnOrBorrow::RefCellRef(ref_cell)),
+            OwnOrWeak::RefCellRc(ref_cell) => Some(OwnOrBorrow::RefCellRef(ref_cell)),
+            OwnOrWeak::RefCellWeak(weak) => weak.upgrade().map(OwnOrBorrow::RefCellRc),
+        }
+    }
+
+    /// Like [`upgrade`](Self::upgrade), but lends owned values as
+    /// [`OwnOrBorrow::BorrowedMut`].
+    pub fn upgrade_mut(&mut self) -> Option<OwnOrBorrow<'_, T>> {
+        match self {
+            OwnOrWeak::Owned(value) => Some(OwnOrBorrow::BorrowedMut(value)),
+            other => other.upgrade(),
+        }
+    }
+
+    /// Runs the closure with shared access to the inner value, or returns `None` if the
+    /// value was dropped.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently mutably borrowed.
+    pub fn with<R, F>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.upgrade().map(|value| value.with(f))
+    }
+
+    /// Runs the closure with mutable access to the inner value, or returns `None` if the
+    /// value was dropped.
+    ///
+    /// ## Panics
+    /// Panics if the value is currently borrowed.
+    pub fn with_mut<R, F>(&mut self, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.upgrade_mut().map(|mut value| value.with_mut(f))
+    }
+}
+
+impl<'a, T> From<&'a RefCell<T>> for OwnOrWeak<'a, T> {
+    #[inline]
+    fn from(value: &'a RefCell<T>) -> Self {
+        Self::RefCellRef(value)
+    }
+}
+
+impl<'a, T> From<Rc<RefCell<T>>> for OwnOrWeak<'a, T> {
+    #[inline]
+    fn from(value: Rc<RefCell<T>>) -> Self {
+        Self::RefCellRc(value)
+    }
+}
+
+impl<'a, T> From<Weak<RefCell<T>>> for OwnOrWeak<'a, T> {
+    #[inline]
+    fn from(value: Weak<RefCell<T>>) -> Self {
+        Self::RefCellWeak(value)
+    }
+}
+
+impl<'a, T> core::fmt::Debug for OwnOrWeak<'a, T>
+where
+    T: core::fmt::Debug,
+{
+    /// Formats the inner value, or prints `<dropped>` if it was dropped.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.upgrade() {
+            Some(value) => core::fmt::Debug::fmt(&value, f),
+            None => f.write_str("<dropped>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weak_does_not_keep_value_alive() {
+        let shared = Rc::new(RefCell::new(42));
+        let mut value = OwnOrWeak::from(Rc::downgrade(&shared));
+        assert!(value.is_alive());
+        assert_eq!(
+            value.with_mut(|value| core::mem::replace(value, 17)),
+            Some(42)
+        );
+        assert_eq!(*shared.borrow(), 17);
+        assert_eq!(Rc::strong_count(&shared), 1);
+
+        drop(shared);
+        assert!(!value.is_alive());
+        assert_eq!(value.with(|value| *value), None);
+    }
+
+    #[test]
+    fn upgrade_keeps_value_alive() {
+        let shared = Rc::new(RefCell::new(42));
+        let value = OwnOrWeak::downgrade(&shared);
+        let upgraded = value.upgrade().expect("value is alive");
+        drop(shared);
+        assert!(value.is_alive());
+        assert_eq!(*upgraded.borrow(), 42);
+        drop(upgraded);
+        assert!(!value.is_alive());
+    }
+
+    #[test]
+    fn owned_value_is_always_alive() {
+        let mut value = OwnOrWeak::own(42);
+        assert!(value.is_alive());
+        assert_eq!(value.with_mut(|value| *value += 1), Some(()));
+        let upgraded = value.upgrade().expect("value is owned");
+        assert!(matches!(upgraded, OwnOrBorrow::Borrowed(&43)));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn debug_dropped_value() {
+        let shared = Rc::new(RefCell::new(42));
+        let value = OwnOrWeak::downgrade(&shared);
+        assert_eq!(format!("{:?}", value), "42");
+        drop(shared);
+        assert_eq!(format!("{:?}", value), "<dropped>");
+    }
+}